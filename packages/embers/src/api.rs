@@ -1,6 +1,7 @@
 pub mod agents;
 pub mod agents_teams;
-mod common;
+pub mod common;
+pub mod explorer;
 pub mod oslfs;
 pub mod service;
 pub mod testnet;
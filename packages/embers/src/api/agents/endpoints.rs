@@ -9,6 +9,7 @@ use crate::api::agents::models::{
     Agents,
     CreateAgentReq,
     CreateAgentResp,
+    DeleteAgentReq,
     DeleteAgentResp,
     DeployAgentReq,
     DeployAgentResp,
@@ -20,10 +21,13 @@ use crate::api::common::{
     ApiTags,
     MaybeNotFound,
     PrepareResponse,
+    SendRejection,
     SendRequest,
+    SendRequestError,
     SendResp,
     SignedContract,
     Stringified,
+    TokenKeys,
 };
 use crate::domain::agents::AgentsService;
 
@@ -68,12 +72,12 @@ impl AgentsApi {
         &self,
         Json(body): Json<CreateAgentReq>,
         Data(agents): Data<&AgentsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<CreateAgentResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents.prepare_create_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -95,12 +99,12 @@ impl AgentsApi {
         &self,
         Json(body): Json<DeployAgentReq>,
         Data(agents): Data<&AgentsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<DeployAgentResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents.prepare_deploy_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -123,12 +127,12 @@ impl AgentsApi {
         Path(id): Path<String>,
         Json(body): Json<SaveAgentReq>,
         Data(agents): Data<&AgentsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<SaveAgentResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents.prepare_save_contract(id, body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -151,19 +155,34 @@ impl AgentsApi {
         &self,
         Path(id): Path<String>,
         Data(agents): Data<&AgentsService>,
-    ) -> poem::Result<Json<DeleteAgentResp>> {
-        let contract = agents.prepare_delete_contract(id).await?;
-        Ok(Json(contract.into()))
+        Data(token_keys): Data<&TokenKeys>,
+    ) -> poem::Result<Json<PrepareResponse<DeleteAgentResp>>> {
+        PrepareResponse::from_call(
+            DeleteAgentReq { id },
+            |request| agents.prepare_delete_contract(request.id),
+            token_keys,
+        )
+        .await
+        .map(Json)
+        .map_err(Into::into)
     }
 
     #[oai(path = "/:id/delete/send", method = "post")]
     async fn delete(
         &self,
-        #[allow(unused_variables)] Path(id): Path<String>,
-        Json(body): Json<SignedContract>,
+        Path(id): Path<String>,
+        SendRequest(body): SendRequest<SignedContract, DeleteAgentReq, DeleteAgentResp>,
         Data(agents): Data<&AgentsService>,
     ) -> poem::Result<Json<SendResp>> {
-        let deploy_id = agents.deploy_signed_delete(body.into()).await?;
+        if body.prepare_request.id != id {
+            return Err(SendRequestError::new(
+                SendRejection::PayloadMismatch,
+                "prepare request is for another id",
+            )
+            .into());
+        }
+
+        let deploy_id = agents.deploy_signed_delete(body.request.into()).await?;
         Ok(Json(deploy_id.into()))
     }
 }
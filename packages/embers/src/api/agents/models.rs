@@ -3,7 +3,14 @@ use firefly_client::models::WalletAddress;
 use poem_openapi::{Object, Union};
 use structural_convert::StructuralConvert;
 
-use crate::api::common::{PreparedContract, SignedContract, Stringified};
+use crate::api::common::{
+    ExpectedDeployer,
+    PreparedContract,
+    PreparedContracts,
+    SignedContract,
+    SignedContracts,
+    Stringified,
+};
 use crate::domain::agents::models;
 use crate::domain::common::PositiveNonZero;
 
@@ -36,6 +43,8 @@ pub struct CreateAgentReq {
     pub code: Option<String>,
 }
 
+impl ExpectedDeployer for CreateAgentReq {}
+
 #[derive(Debug, Clone, StructuralConvert, Object)]
 #[convert(from(models::Agent))]
 pub struct Agent {
@@ -58,6 +67,12 @@ pub struct CreateAgentResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for CreateAgentResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 pub type SaveAgentReq = CreateAgentReq;
 
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
@@ -67,12 +82,32 @@ pub struct SaveAgentResp {
     pub contract: PreparedContract,
 }
 
-#[derive(Debug, Clone, StructuralConvert, Object)]
+impl PreparedContracts for SaveAgentResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
+/// Built from the path of `delete/prepare`, sent back as `prepare_request` to `delete/send`.
+#[derive(Debug, Clone, Hash, Object)]
+pub struct DeleteAgentReq {
+    pub id: String,
+}
+
+impl ExpectedDeployer for DeleteAgentReq {}
+
+#[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(from(models::DeleteResp))]
 pub struct DeleteAgentResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for DeleteAgentResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 #[derive(Debug, Clone, Hash, Object)]
 pub struct DeployAgent {
     id: String,
@@ -94,6 +129,8 @@ pub enum DeployAgentReq {
     Code(DeployCode),
 }
 
+impl ExpectedDeployer for DeployAgentReq {}
+
 impl From<DeployAgentReq> for models::DeployReq {
     fn from(value: DeployAgentReq) -> Self {
         match value {
@@ -118,9 +155,21 @@ pub struct DeployAgentResp {
     pub system: Option<PreparedContract>,
 }
 
+impl PreparedContracts for DeployAgentResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract), self.system.as_ref()]
+    }
+}
+
 #[derive(Debug, Clone, StructuralConvert, Object)]
 #[convert(into(models::DeploySignedReq))]
 pub struct DeploySignedAgentReq {
     pub contract: SignedContract,
     pub system: Option<SignedContract>,
 }
+
+impl SignedContracts for DeploySignedAgentReq {
+    fn signed_contracts(&self) -> Vec<Option<&SignedContract>> {
+        vec![Some(&self.contract), self.system.as_ref()]
+    }
+}
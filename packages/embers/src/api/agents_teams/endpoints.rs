@@ -9,6 +9,7 @@ use crate::api::agents_teams::models::{
     AgentsTeams,
     CreateAgentsTeamReq,
     CreateAgentsTeamResp,
+    DeleteAgentsTeamReq,
     DeleteAgentsTeamResp,
     DeployAgentsTeamReq,
     DeployAgentsTeamResp,
@@ -18,6 +19,7 @@ use crate::api::agents_teams::models::{
     PublishToFireskyResp,
     RunReq,
     RunResp,
+    RunResult,
     SaveAgentsTeamReq,
     SaveAgentsTeamResp,
 };
@@ -25,10 +27,13 @@ use crate::api::common::{
     ApiTags,
     MaybeNotFound,
     PrepareResponse,
+    SendRejection,
     SendRequest,
+    SendRequestError,
     SendResp,
     SignedContract,
     Stringified,
+    TokenKeys,
 };
 use crate::domain::agents_teams::{AgentsTeamsService, models};
 
@@ -73,12 +78,12 @@ impl AgentsTeamsApi {
         &self,
         Json(body): Json<CreateAgentsTeamReq>,
         Data(agents_teams): Data<&AgentsTeamsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<CreateAgentsTeamResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents_teams.prepare_create_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -102,12 +107,12 @@ impl AgentsTeamsApi {
         &self,
         Json(body): Json<DeployAgentsTeamReq>,
         Data(agents_teams): Data<&AgentsTeamsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<DeployAgentsTeamResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents_teams.prepare_deploy_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -135,12 +140,12 @@ impl AgentsTeamsApi {
         &self,
         Json(body): Json<RunReq>,
         Data(agents_teams): Data<&AgentsTeamsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<RunResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents_teams.prepare_run_agents_team_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -152,10 +157,11 @@ impl AgentsTeamsApi {
         &self,
         SendRequest(body): SendRequest<SignedContract, RunReq, RunResp>,
         Data(agents_teams): Data<&AgentsTeamsService>,
-    ) -> poem::Result<Json<serde_json::Value>> {
+    ) -> poem::Result<Json<RunResult>> {
         agents_teams
             .deploy_signed_run_agents_team(body.request.into())
             .await
+            .map(Into::into)
             .map(Json)
             .map_err(Into::into)
     }
@@ -165,12 +171,12 @@ impl AgentsTeamsApi {
         &self,
         Json(body): Json<RunReq>,
         Data(agents_teams): Data<&AgentsTeamsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<RunResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents_teams.prepare_run_om_firesky_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -199,12 +205,12 @@ impl AgentsTeamsApi {
         Path(id): Path<String>,
         Json(body): Json<SaveAgentsTeamReq>,
         Data(agents_teams): Data<&AgentsTeamsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<SaveAgentsTeamResp>>> {
         PrepareResponse::from_call(
             body,
             |body| agents_teams.prepare_save_contract(id, body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -227,19 +233,34 @@ impl AgentsTeamsApi {
         &self,
         Path(id): Path<String>,
         Data(agents): Data<&AgentsTeamsService>,
-    ) -> poem::Result<Json<DeleteAgentsTeamResp>> {
-        let contract = agents.prepare_delete_contract(id).await?;
-        Ok(Json(contract.into()))
+        Data(token_keys): Data<&TokenKeys>,
+    ) -> poem::Result<Json<PrepareResponse<DeleteAgentsTeamResp>>> {
+        PrepareResponse::from_call(
+            DeleteAgentsTeamReq { id },
+            |request| agents.prepare_delete_contract(request.id),
+            token_keys,
+        )
+        .await
+        .map(Json)
+        .map_err(Into::into)
     }
 
     #[oai(path = "/:id/delete/send", method = "post")]
     async fn delete(
         &self,
-        #[allow(unused_variables)] Path(id): Path<String>,
-        Json(body): Json<SignedContract>,
+        Path(id): Path<String>,
+        SendRequest(body): SendRequest<SignedContract, DeleteAgentsTeamReq, DeleteAgentsTeamResp>,
         Data(agents): Data<&AgentsTeamsService>,
     ) -> poem::Result<Json<SendResp>> {
-        let deploy_id = agents.deploy_signed_delete(body.into()).await?;
+        if body.prepare_request.id != id {
+            return Err(SendRequestError::new(
+                SendRejection::PayloadMismatch,
+                "prepare request is for another id",
+            )
+            .into());
+        }
+
+        let deploy_id = agents.deploy_signed_delete(body.request.into()).await?;
         Ok(Json(deploy_id.into()))
     }
 
@@ -250,14 +271,14 @@ impl AgentsTeamsApi {
         Path(id): Path<String>,
         Json(body): Json<PublishToFireskyReq>,
         Data(agents_teams): Data<&AgentsTeamsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<PublishToFireskyResp>>> {
         PrepareResponse::from_call(
             body,
             |body| {
                 agents_teams.prepare_publish_to_firesky_contract(address.into(), id, body.into())
             },
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
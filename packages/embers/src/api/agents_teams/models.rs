@@ -3,7 +3,15 @@ use firefly_client::models::{Uri, WalletAddress};
 use poem_openapi::{Object, Union};
 use structural_convert::StructuralConvert;
 
-use crate::api::common::{PreparedContract, RegistryDeploy, SignedContract, Stringified};
+use crate::api::common::{
+    ExpectedDeployer,
+    PreparedContract,
+    PreparedContracts,
+    RegistryDeploy,
+    SignedContract,
+    SignedContracts,
+    Stringified,
+};
 use crate::domain::agents_teams::models;
 use crate::domain::common::PositiveNonZero;
 
@@ -37,6 +45,8 @@ pub struct CreateAgentsTeamReq {
     pub graph: Option<Stringified<models::Graph>>,
 }
 
+impl ExpectedDeployer for CreateAgentsTeamReq {}
+
 #[derive(Debug, Clone, StructuralConvert, Object)]
 #[convert(from(models::AgentsTeam))]
 pub struct AgentsTeam {
@@ -60,6 +70,12 @@ pub struct CreateAgentsTeamResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for CreateAgentsTeamResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 pub type SaveAgentsTeamReq = CreateAgentsTeamReq;
 
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
@@ -69,12 +85,32 @@ pub struct SaveAgentsTeamResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for SaveAgentsTeamResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
+/// Built from the path of `delete/prepare`, sent back as `prepare_request` to `delete/send`.
+#[derive(Debug, Clone, Hash, Object)]
+pub struct DeleteAgentsTeamReq {
+    pub id: String,
+}
+
+impl ExpectedDeployer for DeleteAgentsTeamReq {}
+
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(from(models::DeleteResp))]
 pub struct DeleteAgentsTeamResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for DeleteAgentsTeamResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 #[derive(Debug, Clone, Hash, Object)]
 pub struct DeployAgentsTeam {
     pub id: String,
@@ -98,6 +134,8 @@ pub enum DeployAgentsTeamReq {
     Graph(DeployGraph),
 }
 
+impl ExpectedDeployer for DeployAgentsTeamReq {}
+
 impl From<DeployAgentsTeamReq> for models::DeployReq {
     fn from(value: DeployAgentsTeamReq) -> Self {
         match value {
@@ -124,6 +162,12 @@ pub struct DeployAgentsTeamResp {
     pub system: Option<PreparedContract>,
 }
 
+impl PreparedContracts for DeployAgentsTeamResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract), self.system.as_ref()]
+    }
+}
+
 #[derive(Debug, Clone, StructuralConvert, Object)]
 #[convert(into(models::DeploySignedReq))]
 pub struct DeploySignedAgentsTeamReq {
@@ -131,6 +175,12 @@ pub struct DeploySignedAgentsTeamReq {
     pub system: Option<SignedContract>,
 }
 
+impl SignedContracts for DeploySignedAgentsTeamReq {
+    fn signed_contracts(&self) -> Vec<Option<&SignedContract>> {
+        vec![Some(&self.contract), self.system.as_ref()]
+    }
+}
+
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(into(models::RunReq))]
 pub struct RunReq {
@@ -139,12 +189,50 @@ pub struct RunReq {
     pub agents_team: Stringified<Uri>,
 }
 
+impl ExpectedDeployer for RunReq {}
+
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(from(models::RunResp))]
 pub struct RunResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for RunResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
+#[derive(Debug, Clone, Object)]
+pub struct RunOk {
+    pub cost: Stringified<u64>,
+    pub result: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Object)]
+pub struct RunErrored {
+    pub cost: Stringified<u64>,
+}
+
+#[derive(Debug, Clone, Union)]
+#[oai(one_of = true, discriminator_name = "type")]
+pub enum RunResult {
+    Ok(RunOk),
+    Errored(RunErrored),
+}
+
+impl From<models::RunResult> for RunResult {
+    fn from(value: models::RunResult) -> Self {
+        match value {
+            models::RunResult::Ok { cost, result } => Self::Ok(RunOk {
+                cost: cost.into(),
+                result,
+            }),
+            models::RunResult::Errored { cost } => Self::Errored(RunErrored { cost: cost.into() }),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(into(models::PublishToFireskyReq))]
 pub struct PublishToFireskyReq {
@@ -155,18 +243,32 @@ pub struct PublishToFireskyReq {
     pub invite_code: Option<String>,
 }
 
+impl ExpectedDeployer for PublishToFireskyReq {}
+
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(from(models::PublishToFireskyResp))]
 pub struct PublishToFireskyResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for PublishToFireskyResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 #[derive(Debug, Clone, Object)]
 pub struct DeploySignedRunOnFireskyReq {
     pub contract: SignedContract,
     pub reply_to: Option<FireskyReply>,
 }
 
+impl SignedContracts for DeploySignedRunOnFireskyReq {
+    fn signed_contracts(&self) -> Vec<Option<&SignedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 #[derive(Debug, Clone, StructuralConvert, Object)]
 #[convert(into(models::FireskyReply))]
 pub struct FireskyReply {
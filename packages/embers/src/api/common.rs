@@ -1,14 +1,18 @@
-use std::any::TypeId;
 use std::borrow::Cow;
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::collections::HashMap;
 use std::str::FromStr;
 
+use anyhow::Context;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
 use chrono::{DateTime, Utc};
 use derive_more::From;
+use firefly_client::errors::SignedContractError;
 use firefly_client::helpers::ShortHex;
 use firefly_client::models::{DeployId, Uri, WalletAddress};
-use poem::FromRequest;
+use poem::http::StatusCode;
 use poem::web::Data;
+use poem::{FromRequest, IntoResponse};
 use poem_openapi::payload::Json;
 use poem_openapi::registry::{MetaSchema, MetaSchemaRef, Registry};
 use poem_openapi::types::{
@@ -20,7 +24,7 @@ use poem_openapi::types::{
     ToJSON,
     Type,
 };
-use poem_openapi::{ApiExtractor, ApiResponse, NewType, Object, Tags};
+use poem_openapi::{ApiExtractor, ApiResponse, Enum, NewType, Object, Tags};
 use secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
 
@@ -345,6 +349,7 @@ pub enum ApiTags {
     AIAgentsTeams,
     Service,
     Oslfs,
+    Explorer,
 }
 
 #[derive(Debug, Clone, Object)]
@@ -427,6 +432,55 @@ impl From<SignedContract> for firefly_client::models::SignedCode {
     }
 }
 
+/// Signed contracts a send request forwards to the node.
+pub trait SignedContracts {
+    /// Contracts in the same order as [`PreparedContracts::prepared_contracts`].
+    fn signed_contracts(&self) -> Vec<Option<&SignedContract>>;
+}
+
+impl SignedContracts for SignedContract {
+    fn signed_contracts(&self) -> Vec<Option<&SignedContract>> {
+        vec![Some(self)]
+    }
+}
+
+/// Contracts embers prepared for signing.
+pub trait PreparedContracts {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>>;
+}
+
+/// Wallet the prepared contracts have to be signed by.
+pub trait ExpectedDeployer {
+    fn expected_deployer(&self) -> Option<&WalletAddress> {
+        None
+    }
+}
+
+fn verify_signed_contracts(
+    signed: &impl SignedContracts,
+    prepared: &impl PreparedContracts,
+    expected_deployer: Option<&WalletAddress>,
+) -> Result<(), SignedContractError> {
+    let signed = signed.signed_contracts();
+    let prepared = prepared.prepared_contracts();
+
+    if signed.len() != prepared.len() {
+        return Err(SignedContractError::ContractMismatch);
+    }
+
+    signed
+        .into_iter()
+        .zip(prepared)
+        .try_for_each(|pair| match pair {
+            (Some(signed), Some(prepared)) => {
+                firefly_client::models::SignedCode::from(signed.clone())
+                    .verify(&prepared.0.0, expected_deployer)
+            }
+            (Some(_), None) => Err(SignedContractError::ContractMismatch),
+            (None, _) => Ok(()),
+        })
+}
+
 #[derive(Debug, Clone, Hash, Object)]
 pub struct RegistryDeploy {
     pub timestamp: Stringified<DateTime<Utc>>,
@@ -462,7 +516,43 @@ impl From<DeployId> for SendResp {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub exp: u64,
-    pub hash: u64,
+    pub hash: String,
+}
+
+/// Keys prepare tokens are signed and verified with, see [`crate::configuration::Jwt`].
+#[derive(Clone)]
+pub struct TokenKeys {
+    pub signing_key_id: String,
+    pub signing_key: jsonwebtoken::EncodingKey,
+    pub verification_keys: HashMap<String, jsonwebtoken::DecodingKey>,
+    pub ttl: chrono::Duration,
+}
+
+impl TokenKeys {
+    fn encode(&self, hash: String) -> String {
+        let header = jsonwebtoken::Header {
+            kid: Some(self.signing_key_id.clone()),
+            ..Default::default()
+        };
+        let exp = (Utc::now() + self.ttl).timestamp() as _;
+
+        jsonwebtoken::encode(&header, &Claims { exp, hash }, &self.signing_key).unwrap()
+    }
+
+    fn decode(&self, token: &str) -> anyhow::Result<Claims> {
+        let kid = jsonwebtoken::decode_header(token)?
+            .kid
+            .context("token key id is missing")?;
+
+        let key = self
+            .verification_keys
+            .get(&kid)
+            .with_context(|| format!("unknown token key id {kid}"))?;
+
+        jsonwebtoken::decode(token, key, &Default::default())
+            .map(|data| data.claims)
+            .map_err(Into::into)
+    }
 }
 
 #[derive(Debug, Clone, Object)]
@@ -481,31 +571,100 @@ where
     pub async fn from_call<R, P, F, I, E>(
         request: R,
         closure: F,
-        secret: &jsonwebtoken::EncodingKey,
+        token_keys: &TokenKeys,
     ) -> Result<Self, E>
     where
-        R: Hash + 'static,
-        T: Hash + 'static + From<P>,
+        R: Type + ToJSON,
+        T: From<P>,
         F: FnOnce(R) -> I,
         F::Output: Future<Output = Result<P, E>>,
     {
-        let mut h = DefaultHasher::new();
-        (&request, TypeId::of::<R>()).hash(&mut h);
-
+        let request_json = canonical_json(&request);
         let response = closure(request).await?.into();
-        (&response, TypeId::of::<T>()).hash(&mut h);
-        let hash = h.finish();
-
-        let exp = (Utc::now() + chrono::Days::new(1)).timestamp() as _;
+        let hash = payload_hash::<R, T>(&request_json, &canonical_json(&response));
 
         Ok(Self {
             response,
-            token: jsonwebtoken::encode(&Default::default(), &Claims { exp, hash }, secret)
-                .unwrap(),
+            token: token_keys.encode(hash),
         })
     }
 }
 
+fn canonical_json<T: ToJSON>(value: &T) -> String {
+    let mut json = value.to_json().unwrap_or_default();
+    json.sort_all_objects();
+    json.to_string()
+}
+
+/// Hash bound into prepare tokens. Schema names tag the endpoint so equal payloads of different
+/// endpoints get different hashes, and unlike `TypeId` they are stable across builds.
+fn payload_hash<R: Type, C: Type>(request_json: &str, response_json: &str) -> String {
+    let hash = Blake2b::<U32>::new()
+        .chain_update(R::name().as_bytes())
+        .chain_update(b"\0")
+        .chain_update(request_json)
+        .chain_update(b"\0")
+        .chain_update(C::name().as_bytes())
+        .chain_update(b"\0")
+        .chain_update(response_json)
+        .finalize();
+    hex::encode(hash)
+}
+
+/// Why a send request was rejected before anything was deployed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Enum)]
+#[oai(rename_all = "snake_case")]
+pub enum SendRejection {
+    InvalidToken,
+    PayloadMismatch,
+    ContractMismatch,
+    UnsupportedSigAlgorithm,
+    InvalidDeployer,
+    InvalidSignature,
+    DeployerMismatch,
+}
+
+/// Body of `400` responses to send requests failing verification.
+#[derive(Debug, Clone, Object)]
+pub struct SendRequestError {
+    pub reason: SendRejection,
+    pub description: String,
+}
+
+impl SendRequestError {
+    pub fn new(reason: SendRejection, description: impl std::fmt::Display) -> Self {
+        Self {
+            reason,
+            description: description.to_string(),
+        }
+    }
+}
+
+impl From<SignedContractError> for SendRequestError {
+    fn from(err: SignedContractError) -> Self {
+        let reason = match err {
+            SignedContractError::ContractMismatch => SendRejection::ContractMismatch,
+            SignedContractError::UnsupportedSigAlgorithm(_) => {
+                SendRejection::UnsupportedSigAlgorithm
+            }
+            SignedContractError::InvalidDeployer(_) => SendRejection::InvalidDeployer,
+            SignedContractError::InvalidSignature => SendRejection::InvalidSignature,
+            SignedContractError::DeployerMismatch { .. } => SendRejection::DeployerMismatch,
+        };
+        Self::new(reason, err)
+    }
+}
+
+impl From<SendRequestError> for poem::Error {
+    fn from(err: SendRequestError) -> Self {
+        Self::from_response(
+            Json(err)
+                .with_status(StatusCode::BAD_REQUEST)
+                .into_response(),
+        )
+    }
+}
+
 #[derive(Debug, Clone, Object)]
 pub struct SendRequestBody<T, R, C>
 where
@@ -528,9 +687,9 @@ where
 
 impl<'a, T, R, C> ApiExtractor<'a> for SendRequest<T, R, C>
 where
-    T: Type + ParseFromJSON + ToJSON,
-    R: Type + ParseFromJSON + ToJSON + Hash + 'static,
-    C: Type + ParseFromJSON + ToJSON + Hash + 'static,
+    T: Type + ParseFromJSON + ToJSON + SignedContracts,
+    R: Type + ParseFromJSON + ToJSON + ExpectedDeployer,
+    C: Type + ParseFromJSON + ToJSON + PreparedContracts,
 {
     const TYPES: &'static [poem_openapi::ApiExtractorType] =
         &[poem_openapi::ApiExtractorType::RequestObject];
@@ -541,6 +700,7 @@ where
 
     fn register(registry: &mut Registry) {
         SendRequestBody::<T, R, C>::register(registry);
+        SendRequestError::register(registry);
     }
 
     fn request_meta() -> Option<poem_openapi::registry::MetaRequest> {
@@ -556,32 +716,34 @@ where
             .await?
             .0;
 
-        let key = <Data<&jsonwebtoken::DecodingKey> as FromRequest>::from_request(request, body)
+        let token_keys = <Data<&TokenKeys> as FromRequest>::from_request(request, body)
             .await?
             .0;
 
-        let claims = jsonwebtoken::decode::<Claims>(&payload.token, key, &Default::default())
-            .map_err(|err| poem_openapi::error::ParseRequestPayloadError {
-                reason: err.to_string(),
-            })?;
+        let claims = token_keys
+            .decode(&payload.token)
+            .map_err(|err| SendRequestError::new(SendRejection::InvalidToken, err))?;
 
-        let mut h = DefaultHasher::new();
-        (
-            &payload.prepare_request,
-            TypeId::of::<R>(),
-            &payload.prepare_response,
-            TypeId::of::<C>(),
-        )
-            .hash(&mut h);
-        let hash = h.finish();
+        let hash = payload_hash::<R, C>(
+            &canonical_json(&payload.prepare_request),
+            &canonical_json(&payload.prepare_response),
+        );
 
-        if hash != claims.claims.hash {
-            return Err(poem_openapi::error::ParseRequestPayloadError {
-                reason: "invalid token or request payloads".into(),
-            }
+        if hash != claims.hash {
+            return Err(SendRequestError::new(
+                SendRejection::PayloadMismatch,
+                "invalid token or request payloads",
+            )
             .into());
         }
 
+        verify_signed_contracts(
+            &payload.request,
+            &payload.prepare_response,
+            payload.prepare_request.expected_deployer(),
+        )
+        .map_err(SendRequestError::from)?;
+
         Ok(Self(payload))
     }
 }
@@ -0,0 +1,4 @@
+mod endpoints;
+mod models;
+
+pub use endpoints::*;
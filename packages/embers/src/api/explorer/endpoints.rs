@@ -0,0 +1,47 @@
+use poem::web::Data;
+use poem_openapi::OpenApi;
+use poem_openapi::param::{Path, Query};
+use poem_openapi::payload::Json;
+
+use crate::api::common::ApiTags;
+use crate::api::explorer::models::{Block, BlockSummary};
+use crate::domain::explorer::ExplorerService;
+
+#[derive(Debug, Clone)]
+pub struct ExplorerApi;
+
+#[OpenApi(prefix_path = "/explorer", tag = ApiTags::Explorer)]
+impl ExplorerApi {
+    #[oai(path = "/blocks/:hash", method = "get")]
+    async fn block(
+        &self,
+        Path(hash): Path<String>,
+        Data(explorer): Data<&ExplorerService>,
+    ) -> poem::Result<Json<Block>> {
+        let block = explorer.get_block(hash.into()).await?;
+        Ok(Json(block.into()))
+    }
+
+    /// Blocks with numbers in `from..=to` range, at most 100 of them.
+    #[oai(path = "/blocks", method = "get")]
+    async fn blocks(
+        &self,
+        Query(from): Query<u64>,
+        Query(to): Query<u64>,
+        Data(explorer): Data<&ExplorerService>,
+    ) -> poem::Result<Json<Vec<Block>>> {
+        let blocks = explorer.list_blocks(from, to).await?;
+        Ok(Json(blocks.into_iter().map(Into::into).collect()))
+    }
+
+    /// Latest `depth` blocks of the main chain, newest first, at most 100 of them.
+    #[oai(path = "/main-chain", method = "get")]
+    async fn main_chain(
+        &self,
+        Query(depth): Query<u32>,
+        Data(explorer): Data<&ExplorerService>,
+    ) -> poem::Result<Json<Vec<BlockSummary>>> {
+        let blocks = explorer.get_main_chain(depth).await?;
+        Ok(Json(blocks.into_iter().map(Into::into).collect()))
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use firefly_client::block_explorer;
+use poem_openapi::Object;
+use secp256k1::PublicKey;
+use structural_convert::StructuralConvert;
+
+use crate::api::common::Stringified;
+
+#[derive(Debug, Clone, Object, StructuralConvert)]
+#[convert(from(block_explorer::Bond))]
+pub struct Bond {
+    pub validator: Stringified<PublicKey>,
+    pub stake: Stringified<i64>,
+}
+
+#[derive(Debug, Clone, Object, StructuralConvert)]
+#[convert(from(block_explorer::Justification))]
+pub struct Justification {
+    pub validator: Stringified<PublicKey>,
+    pub latest_block: String,
+}
+
+#[derive(Debug, Clone, Object, StructuralConvert)]
+#[convert(from(block_explorer::BlockSummary))]
+pub struct BlockSummary {
+    pub hash: String,
+    pub number: Stringified<u64>,
+    pub timestamp: Stringified<DateTime<Utc>>,
+    pub sender: Option<Stringified<PublicKey>>,
+    pub seq_num: Stringified<u64>,
+    pub shard_id: String,
+    pub parents: Vec<String>,
+    pub justifications: Vec<Justification>,
+    pub bonds: Vec<Bond>,
+    pub pre_state_hash: String,
+    pub post_state_hash: String,
+    pub deploy_count: u32,
+    pub fault_tolerance: f32,
+    pub rejected_deploys: Vec<String>,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Clone, Object, StructuralConvert)]
+#[convert(from(block_explorer::BlockDeploy))]
+pub struct BlockDeploy {
+    pub id: String,
+    pub deployer: Stringified<PublicKey>,
+    pub term: String,
+    pub timestamp: Stringified<DateTime<Utc>>,
+    pub phlo_price: Stringified<u64>,
+    pub phlo_limit: Stringified<u64>,
+    pub valid_after_block_number: Stringified<u64>,
+    pub cost: Stringified<u64>,
+    pub errored: bool,
+    pub system_deploy_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Object, StructuralConvert)]
+#[convert(from(block_explorer::Block))]
+pub struct Block {
+    pub summary: BlockSummary,
+    pub deploys: Vec<BlockDeploy>,
+}
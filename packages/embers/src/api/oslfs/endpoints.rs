@@ -8,14 +8,18 @@ use crate::api::common::{
     ApiTags,
     MaybeNotFound,
     PrepareResponse,
+    SendRejection,
     SendRequest,
+    SendRequestError,
     SendResp,
     SignedContract,
     Stringified,
+    TokenKeys,
 };
 use crate::api::oslfs::models::{
     CreateOslfReq,
     CreateOslfResp,
+    DeleteOslfReq,
     DeleteOslfResp,
     Oslf,
     Oslfs,
@@ -65,12 +69,12 @@ impl OslfsApi {
         &self,
         Json(body): Json<CreateOslfReq>,
         Data(oslfs): Data<&OslfsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<CreateOslfResp>>> {
         PrepareResponse::from_call(
             body,
             |body| oslfs.prepare_create_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -93,12 +97,12 @@ impl OslfsApi {
         Path(id): Path<String>,
         Json(body): Json<SaveOslfReq>,
         Data(oslfs): Data<&OslfsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<SaveOslfResp>>> {
         PrepareResponse::from_call(
             body,
             |body| oslfs.prepare_save_contract(id, body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -121,19 +125,34 @@ impl OslfsApi {
         &self,
         Path(id): Path<String>,
         Data(oslfs): Data<&OslfsService>,
-    ) -> poem::Result<Json<DeleteOslfResp>> {
-        let contract = oslfs.prepare_delete_contract(id).await?;
-        Ok(Json(contract.into()))
+        Data(token_keys): Data<&TokenKeys>,
+    ) -> poem::Result<Json<PrepareResponse<DeleteOslfResp>>> {
+        PrepareResponse::from_call(
+            DeleteOslfReq { id },
+            |request| oslfs.prepare_delete_contract(request.id),
+            token_keys,
+        )
+        .await
+        .map(Json)
+        .map_err(Into::into)
     }
 
     #[oai(path = "/:id/delete/send", method = "post")]
     async fn delete(
         &self,
-        #[allow(unused_variables)] Path(id): Path<String>,
-        Json(body): Json<SignedContract>,
+        Path(id): Path<String>,
+        SendRequest(body): SendRequest<SignedContract, DeleteOslfReq, DeleteOslfResp>,
         Data(oslfs): Data<&OslfsService>,
     ) -> poem::Result<Json<SendResp>> {
-        let deploy_id = oslfs.deploy_signed_delete(body.into()).await?;
+        if body.prepare_request.id != id {
+            return Err(SendRequestError::new(
+                SendRejection::PayloadMismatch,
+                "prepare request is for another id",
+            )
+            .into());
+        }
+
+        let deploy_id = oslfs.deploy_signed_delete(body.request.into()).await?;
         Ok(Json(deploy_id.into()))
     }
 }
@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use poem_openapi::Object;
 use structural_convert::StructuralConvert;
 
-use crate::api::common::{PreparedContract, Stringified};
+use crate::api::common::{ExpectedDeployer, PreparedContract, PreparedContracts, Stringified};
 use crate::domain::oslfs::models;
 
 #[derive(Debug, Clone, StructuralConvert, Object)]
@@ -40,6 +40,8 @@ pub struct CreateOslfReq {
     pub query: Option<String>,
 }
 
+impl ExpectedDeployer for CreateOslfReq {}
+
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(from(models::CreateResp))]
 pub struct CreateOslfResp {
@@ -48,6 +50,12 @@ pub struct CreateOslfResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for CreateOslfResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 pub type SaveOslfReq = CreateOslfReq;
 
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
@@ -57,8 +65,28 @@ pub struct SaveOslfResp {
     pub contract: PreparedContract,
 }
 
-#[derive(Debug, Clone, StructuralConvert, Object)]
+impl PreparedContracts for SaveOslfResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
+/// Built from the path of `delete/prepare`, sent back as `prepare_request` to `delete/send`.
+#[derive(Debug, Clone, Hash, Object)]
+pub struct DeleteOslfReq {
+    pub id: String,
+}
+
+impl ExpectedDeployer for DeleteOslfReq {}
+
+#[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(from(models::DeleteResp))]
 pub struct DeleteOslfResp {
     pub contract: PreparedContract,
 }
+
+impl PreparedContracts for DeleteOslfResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
@@ -2,7 +2,7 @@ use poem::web::Data;
 use poem_openapi::OpenApi;
 use poem_openapi::payload::Json;
 
-use crate::api::common::{ApiTags, PrepareResponse, SendRequest};
+use crate::api::common::{ApiTags, PrepareResponse, SendRequest, TokenKeys};
 use crate::api::testnet::models::{
     CreateTestwalletResp,
     DeploySignedTestReq,
@@ -31,12 +31,12 @@ impl TestnetApi {
         &self,
         Json(body): Json<DeployTestReq>,
         Data(testnet): Data<&TestnetService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<DeployTestResp>>> {
         PrepareResponse::from_call(
             body,
             |body| testnet.prepare_test_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -1,7 +1,14 @@
 use poem_openapi::{Enum, Object, Union};
 use structural_convert::StructuralConvert;
 
-use crate::api::common::{PreparedContract, SignedContract};
+use crate::api::common::{
+    ExpectedDeployer,
+    PreparedContract,
+    PreparedContracts,
+    SignedContract,
+    SignedContracts,
+    Stringified,
+};
 use crate::domain::testnet::models;
 
 #[derive(Debug, Clone, Object)]
@@ -24,6 +31,12 @@ pub struct DeployTestResp {
     pub test_contract: PreparedContract,
 }
 
+impl PreparedContracts for DeployTestResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![self.env_contract.as_ref(), Some(&self.test_contract)]
+    }
+}
+
 #[derive(Debug, Clone, StructuralConvert, Object)]
 #[convert(into(models::DeploySignedTestReq))]
 pub struct DeploySignedTestReq {
@@ -31,6 +44,12 @@ pub struct DeploySignedTestReq {
     pub test: SignedContract,
 }
 
+impl SignedContracts for DeploySignedTestReq {
+    fn signed_contracts(&self) -> Vec<Option<&SignedContract>> {
+        vec![self.env.as_ref(), Some(&self.test)]
+    }
+}
+
 #[derive(Debug, Clone, Hash, StructuralConvert, Object)]
 #[convert(into(models::DeployTestReq))]
 pub struct DeployTestReq {
@@ -38,6 +57,8 @@ pub struct DeployTestReq {
     pub test: String,
 }
 
+impl ExpectedDeployer for DeployTestReq {}
+
 #[derive(Debug, Clone, Object)]
 pub struct EnvDeployFailed {
     pub error: String,
@@ -66,6 +87,7 @@ pub struct Log {
 
 #[derive(Debug, Clone, Object)]
 pub struct SignedTestDeployLogs {
+    pub cost: Stringified<u64>,
     pub logs: Vec<Log>,
 }
 
@@ -74,6 +96,7 @@ pub struct SignedTestDeployLogs {
 pub enum DeploySignedTestResp {
     EnvDeployFailed(EnvDeployFailed),
     TestDeployFailed(TestDeployFailed),
+    TestDeployErrored(SignedTestDeployLogs),
     Ok(SignedTestDeployLogs),
 }
 
@@ -86,7 +109,14 @@ impl From<models::DeploySignedTestResp> for DeploySignedTestResp {
             models::DeploySignedTestResp::TestDeployFailed { error } => {
                 Self::TestDeployFailed(TestDeployFailed { error })
             }
-            models::DeploySignedTestResp::Ok { logs } => Self::Ok(SignedTestDeployLogs {
+            models::DeploySignedTestResp::TestDeployErrored { cost, logs } => {
+                Self::TestDeployErrored(SignedTestDeployLogs {
+                    cost: cost.into(),
+                    logs: logs.into_iter().map(Into::into).collect(),
+                })
+            }
+            models::DeploySignedTestResp::Ok { cost, logs } => Self::Ok(SignedTestDeployLogs {
+                cost: cost.into(),
                 logs: logs.into_iter().map(Into::into).collect(),
             }),
         }
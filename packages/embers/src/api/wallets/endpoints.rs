@@ -14,6 +14,7 @@ use crate::api::common::{
     SendResp,
     SignedContract,
     Stringified,
+    TokenKeys,
 };
 use crate::api::wallets::models::{
     BoostReq,
@@ -49,12 +50,12 @@ impl WalletsApi {
         &self,
         Json(body): Json<TransferReq>,
         Data(wallets): Data<&WalletsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<TransferResp>>> {
         PrepareResponse::from_call(
             body,
             |body| wallets.prepare_transfer_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -76,12 +77,12 @@ impl WalletsApi {
         &self,
         Json(body): Json<BoostReq>,
         Data(wallets): Data<&WalletsService>,
-        Data(encoding_key): Data<&jsonwebtoken::EncodingKey>,
+        Data(token_keys): Data<&TokenKeys>,
     ) -> poem::Result<Json<PrepareResponse<BoostResp>>> {
         PrepareResponse::from_call(
             body,
             |body| wallets.prepare_boost_contract(body.into()),
-            encoding_key,
+            token_keys,
         )
         .await
         .map(Json)
@@ -3,7 +3,7 @@ use firefly_client::models::WalletAddress;
 use poem_openapi::{Enum, Object, Union};
 use structural_convert::StructuralConvert;
 
-use crate::api::common::{PreparedContract, Stringified};
+use crate::api::common::{ExpectedDeployer, PreparedContract, PreparedContracts, Stringified};
 use crate::domain::common::PositiveNonZero;
 use crate::domain::wallets::models;
 
@@ -72,12 +72,24 @@ pub struct TransferReq {
     pub description: Option<String>,
 }
 
+impl ExpectedDeployer for TransferReq {
+    fn expected_deployer(&self) -> Option<&WalletAddress> {
+        Some(&self.from.0)
+    }
+}
+
 #[derive(Debug, Clone, Hash, Object, StructuralConvert)]
 #[convert(from(models::TransferResp))]
 pub struct TransferResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for TransferResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 #[derive(Debug, Clone, Hash, Object, StructuralConvert)]
 #[convert(into(models::BoostReq))]
 pub struct BoostReq {
@@ -89,12 +101,24 @@ pub struct BoostReq {
     pub post_id: Option<String>,
 }
 
+impl ExpectedDeployer for BoostReq {
+    fn expected_deployer(&self) -> Option<&WalletAddress> {
+        Some(&self.from.0)
+    }
+}
+
 #[derive(Debug, Clone, Hash, Object, StructuralConvert)]
 #[convert(from(models::BoostResp))]
 pub struct BoostResp {
     pub contract: PreparedContract,
 }
 
+impl PreparedContracts for BoostResp {
+    fn prepared_contracts(&self) -> Vec<Option<&PreparedContract>> {
+        vec![Some(&self.contract)]
+    }
+}
+
 #[derive(Debug, Clone, Enum, StructuralConvert)]
 #[convert(from(models::NodeType))]
 pub enum NodeType {
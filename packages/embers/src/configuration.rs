@@ -1,32 +1,78 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
 use anyhow::Context;
 use figment::Figment;
 use figment::providers::Env;
+use firefly_client::keystore::KeySource;
 use secp256k1::SecretKey;
 use serde::Deserialize;
 
+/// Validator to fail over to, proposing deploys it accepted on its own.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Validator {
+    pub deploy_service_url: String,
+    pub propose_service_url: String,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct MainNet {
     pub deploy_service_url: String,
+    #[serde(default)]
+    pub extra_validators: Vec<Validator>,
     pub propose_service_url: String,
     pub validator_ws_api_url: String,
     pub observer_url: String,
+    pub observer_grpc_url: Option<String>,
     pub observer_ws_api_url: String,
+    #[serde(deserialize_with = "deserialize_key")]
     pub service_key: SecretKey,
+    #[serde(deserialize_with = "deserialize_key")]
     pub wallets_env_key: SecretKey,
+    #[serde(deserialize_with = "deserialize_key")]
     pub agents_env_key: SecretKey,
+    #[serde(deserialize_with = "deserialize_key")]
     pub agents_teams_env_key: SecretKey,
+    #[serde(deserialize_with = "deserialize_key")]
     pub oslfs_env_key: SecretKey,
+    #[serde(default = "default_shard_id")]
+    pub shard_id: String,
+    #[serde(
+        default = "default_phlo_price",
+        deserialize_with = "deserialize_phlo_price"
+    )]
+    pub phlo_price: i64,
+    #[serde(default = "default_propose_interval_ms")]
+    pub propose_interval_ms: u64,
+    #[serde(default = "default_propose_batch_size")]
+    pub propose_batch_size: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct TestNet {
     pub deploy_service_url: String,
+    #[serde(default)]
+    pub extra_validators: Vec<Validator>,
     pub propose_service_url: String,
     pub validator_ws_api_url: String,
     pub observer_url: String,
+    pub observer_grpc_url: Option<String>,
     pub observer_ws_api_url: String,
+    #[serde(deserialize_with = "deserialize_key")]
     pub service_key: SecretKey,
+    #[serde(deserialize_with = "deserialize_key")]
     pub env_key: SecretKey,
+    #[serde(default = "default_shard_id")]
+    pub shard_id: String,
+    #[serde(
+        default = "default_phlo_price",
+        deserialize_with = "deserialize_phlo_price"
+    )]
+    pub phlo_price: i64,
+    #[serde(default = "default_propose_interval_ms")]
+    pub propose_interval_ms: u64,
+    #[serde(default = "default_propose_batch_size")]
+    pub propose_batch_size: usize,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -38,6 +84,57 @@ pub struct Config {
     pub testnet: TestNet,
     #[serde(deserialize_with = "deserialize_hex_key")]
     pub aes_encryption_key: [u8; 32],
+    pub jwt: Jwt,
+    #[serde(default = "default_idempotency_ttl_secs")]
+    pub idempotency_ttl_secs: u64,
+}
+
+/// Secrets prepare/send tokens are signed with.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Jwt {
+    /// Id of the key new tokens are signed with, has to be one of `keys`.
+    pub key_id: String,
+    /// Keys tokens are accepted with by id, keep the previous key here while rotating.
+    pub keys: HashMap<String, JwtSecret>,
+    #[serde(
+        rename = "token_ttl_secs",
+        default = "default_token_ttl",
+        deserialize_with = "deserialize_token_ttl"
+    )]
+    pub token_ttl: chrono::Duration,
+}
+
+/// Jwt secret given inline or as `{ file = <path> }`.
+#[derive(derive_more::Debug, Clone)]
+#[debug("JwtSecret(..)")]
+pub struct JwtSecret(pub Vec<u8>);
+
+impl<'de> Deserialize<'de> for JwtSecret {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Source {
+            Inline(String),
+            File { file: PathBuf },
+        }
+
+        match Source::deserialize(deserializer)? {
+            Source::Inline(secret) => Ok(Self(secret.into_bytes())),
+            Source::File { file } => std::fs::read(&file)
+                .map(|mut secret| {
+                    if secret.last() == Some(&b'\n') {
+                        secret.pop();
+                    }
+                    Self(secret)
+                })
+                .map_err(|err| {
+                    serde::de::Error::custom(format!("failed to read {}: {err}", file.display()))
+                }),
+        }
+    }
 }
 
 pub fn collect_config() -> anyhow::Result<Config> {
@@ -47,6 +144,64 @@ pub fn collect_config() -> anyhow::Result<Config> {
         .context("failed to collect config")
 }
 
+fn default_shard_id() -> String {
+    "root".to_owned()
+}
+
+const fn default_phlo_price() -> i64 {
+    1
+}
+
+const fn default_propose_interval_ms() -> u64 {
+    1000
+}
+
+const fn default_propose_batch_size() -> usize {
+    16
+}
+
+const fn default_token_ttl() -> chrono::Duration {
+    chrono::Duration::days(1)
+}
+
+const fn default_idempotency_ttl_secs() -> u64 {
+    24 * 60 * 60
+}
+
+/// Accepts key in hex or keystore file, see [`KeySource`].
+fn deserialize_key<'de, D>(deserializer: D) -> Result<SecretKey, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    KeySource::deserialize(deserializer)?
+        .load()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Phlo price is signed in deploys, so it has to fit into `i64` on top of being non negative.
+fn deserialize_phlo_price<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let price: u64 = Deserialize::deserialize(deserializer)?;
+    price
+        .try_into()
+        .map_err(|_| serde::de::Error::custom(format!("phlo_price {price} is too large")))
+}
+
+fn deserialize_token_ttl<'de, D>(deserializer: D) -> Result<chrono::Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let secs: u64 = Deserialize::deserialize(deserializer)?;
+    // token expiry is computed from the current time, so it has to stay representable
+    i64::try_from(secs)
+        .ok()
+        .and_then(chrono::Duration::try_seconds)
+        .filter(|ttl| chrono::Utc::now().checked_add_signed(*ttl).is_some())
+        .ok_or_else(|| serde::de::Error::custom(format!("token_ttl_secs {secs} is too large")))
+}
+
 fn deserialize_hex_key<'de, D, const S: usize>(deserializer: D) -> Result<[u8; S], D::Error>
 where
     D: serde::Deserializer<'de>,
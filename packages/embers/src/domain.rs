@@ -1,6 +1,7 @@
 pub mod agents;
 pub mod agents_teams;
 pub mod common;
+pub mod explorer;
 pub mod oslfs;
 pub mod testnet;
 pub mod wallets;
@@ -2,9 +2,11 @@ use anyhow::Context;
 use firefly_client::helpers::insert_signed_signature;
 use firefly_client::models::{DeployData, Uri};
 use firefly_client::rendering::Render;
-use firefly_client::{ReadNodeClient, WriteNodeClient};
+use firefly_client::{Proposer, ReadNodeClient, WriteNodeClient};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 
+use crate::domain::common::DeployDefaults;
+
 mod create;
 mod delete;
 mod deploy;
@@ -17,7 +19,9 @@ mod save;
 #[derive(Clone)]
 pub struct AgentsService {
     pub uri: Uri,
+    pub deploy_defaults: DeployDefaults,
     pub write_client: WriteNodeClient,
+    pub proposer: Proposer,
     pub read_client: ReadNodeClient,
 }
 
@@ -36,9 +40,11 @@ impl AgentsService {
     #[tracing::instrument(level = "info", skip_all, err(Debug))]
     pub async fn bootstrap(
         mut write_client: WriteNodeClient,
+        proposer: Proposer,
         read_client: ReadNodeClient,
         deployer_key: &SecretKey,
         env_key: &SecretKey,
+        deploy_defaults: DeployDefaults,
     ) -> anyhow::Result<Self> {
         let secp = Secp256k1::new();
         let env_public_key = PublicKey::from_secret_key(&secp, env_key);
@@ -59,7 +65,11 @@ impl AgentsService {
 
         tracing::debug!("code = {code}");
 
-        let deploy_data = DeployData::builder(code).timestamp(timestamp).build();
+        let deploy_data = DeployData::builder(code)
+            .timestamp(timestamp)
+            .phlo_price(deploy_defaults.phlo_price)
+            .shard_id(deploy_defaults.shard_id.clone())
+            .build();
 
         write_client
             .deploy(deployer_key, deploy_data)
@@ -68,7 +78,9 @@ impl AgentsService {
 
         Ok(Self {
             uri: env_uri,
+            deploy_defaults,
             write_client,
+            proposer,
             read_client,
         })
     }
@@ -55,6 +55,7 @@ impl AgentsService {
             contract: prepare_for_signing()
                 .code(contract)
                 .valid_after_block_number(valid_after)
+                .defaults(&self.deploy_defaults)
                 .call(),
         })
     }
@@ -72,7 +73,7 @@ impl AgentsService {
         let mut write_client = self.write_client.clone();
 
         let deploy_id = write_client.deploy_signed_contract(contract).await?;
-        write_client.propose().await?;
+        self.proposer.propose(deploy_id.clone()).await?;
 
         Ok(deploy_id)
     }
@@ -28,19 +28,18 @@ impl AgentsService {
         record_trace!(request);
 
         let valid_after = self.write_client.clone().get_head_block_index().await?;
-        let (code, phlo_limit, system) = match request {
+        let (code, shard, phlo_limit, system) = match request {
             DeployReq::Agent {
                 id,
                 version,
                 address,
                 phlo_limit,
             } => {
-                let code = self
+                let agent = self
                     .get(address, id.clone(), version.clone())
                     .await?
-                    .context("agent not found")?
-                    .code
-                    .context("agent has no code")?;
+                    .context("agent not found")?;
+                let code = agent.code.context("agent has no code")?;
 
                 let system_code = UpdateLastDeploy {
                     env_uri: self.uri.clone(),
@@ -52,22 +51,26 @@ impl AgentsService {
 
                 (
                     code,
+                    agent.shard,
                     phlo_limit,
                     Some(
                         prepare_for_signing()
                             .code(system_code)
                             .valid_after_block_number(valid_after)
+                            .defaults(&self.deploy_defaults)
                             .call(),
                     ),
                 )
             }
-            DeployReq::Code { code, phlo_limit } => (code, phlo_limit, None),
+            DeployReq::Code { code, phlo_limit } => (code, None, phlo_limit, None),
         };
 
         Ok(DeployResp {
             contract: prepare_for_signing()
                 .code(code)
                 .valid_after_block_number(valid_after)
+                .defaults(&self.deploy_defaults)
+                .maybe_shard(shard)
                 .phlo_limit(phlo_limit)
                 .call(),
             system,
@@ -90,11 +93,14 @@ impl AgentsService {
             .deploy_signed_contract(request.contract)
             .await?;
 
+        let included = self.proposer.propose(deploy_id.clone());
+
         if let Some(system) = request.system {
-            write_client.deploy_signed_contract(system).await?;
+            let system_deploy_id = write_client.deploy_signed_contract(system).await?;
+            self.proposer.propose(system_deploy_id).await?;
         }
 
-        write_client.propose().await?;
+        included.await?;
         Ok(deploy_id)
     }
 }
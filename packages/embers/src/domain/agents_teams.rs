@@ -7,12 +7,12 @@ use firefly_client::errors::ReadNodeError;
 use firefly_client::helpers::insert_signed_signature;
 use firefly_client::models::{DeployData, Uri};
 use firefly_client::rendering::Render;
-use firefly_client::{NodeEvents, ReadNodeClient, WriteNodeClient};
+use firefly_client::{NodeEvents, Proposer, ReadNodeClient, WriteNodeClient};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 
 use crate::blockchain;
 use crate::domain::agents_teams::models::FireskyCredentials;
-use crate::domain::common::deserialize_decrypted;
+use crate::domain::common::{DeployDefaults, deserialize_decrypted};
 
 mod compilation;
 mod create;
@@ -30,7 +30,9 @@ mod save;
 #[derive(Clone)]
 pub struct AgentsTeamsService {
     pub uri: Uri,
+    pub deploy_defaults: DeployDefaults,
     pub write_client: WriteNodeClient,
+    pub proposer: Proposer,
     pub read_client: ReadNodeClient,
     pub observer_node_events: NodeEvents,
     pub aes_encryption_key: Key<Aes256Gcm>,
@@ -56,13 +58,16 @@ struct GetFireskyTokens {
 
 #[allow(unused)]
 impl AgentsTeamsService {
+    #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(level = "info", skip_all, err(Debug))]
     pub async fn bootstrap(
         mut write_client: WriteNodeClient,
+        proposer: Proposer,
         read_client: ReadNodeClient,
         observer_node_events: NodeEvents,
         deployer_key: &SecretKey,
         env_key: &SecretKey,
+        deploy_defaults: DeployDefaults,
         aes_encryption_key: Key<Aes256Gcm>,
     ) -> anyhow::Result<Self> {
         let secp = Secp256k1::new();
@@ -84,7 +89,11 @@ impl AgentsTeamsService {
 
         tracing::debug!("code = {code}");
 
-        let deploy_data = DeployData::builder(code).timestamp(timestamp).build();
+        let deploy_data = DeployData::builder(code)
+            .timestamp(timestamp)
+            .phlo_price(deploy_defaults.phlo_price)
+            .shard_id(deploy_defaults.shard_id.clone())
+            .build();
 
         write_client
             .deploy(deployer_key, deploy_data)
@@ -132,7 +141,9 @@ impl AgentsTeamsService {
 
         Ok(Self {
             uri: env_uri,
+            deploy_defaults,
             write_client,
+            proposer,
             read_client,
             observer_node_events,
             aes_encryption_key,
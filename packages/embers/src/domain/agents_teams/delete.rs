@@ -26,6 +26,7 @@ impl AgentsTeamsService {
             contract: prepare_for_signing()
                 .code(contract)
                 .valid_after_block_number(valid_after)
+                .defaults(&self.deploy_defaults)
                 .call(),
         })
     }
@@ -43,7 +44,7 @@ impl AgentsTeamsService {
         let mut write_client = self.write_client.clone();
 
         let deploy_id = write_client.deploy_signed_contract(contract).await?;
-        write_client.propose().await?;
+        self.proposer.propose(deploy_id.clone()).await?;
         Ok(deploy_id)
     }
 }
@@ -30,7 +30,7 @@ impl AgentsTeamsService {
         record_trace!(request);
 
         let valid_after = self.write_client.clone().get_head_block_index().await?;
-        let (graph, phlo_limit, deploy, system) = match request {
+        let (graph, shard, phlo_limit, deploy, system) = match request {
             DeployReq::AgentsTeam {
                 id,
                 version,
@@ -55,12 +55,14 @@ impl AgentsTeamsService {
 
                 (
                     graph,
+                    agents_team.shard,
                     phlo_limit,
                     deploy,
                     Some(
                         prepare_for_signing()
                             .code(system_code)
                             .valid_after_block_number(valid_after)
+                            .defaults(&self.deploy_defaults)
                             .call(),
                     ),
                 )
@@ -69,7 +71,7 @@ impl AgentsTeamsService {
                 graph,
                 phlo_limit,
                 deploy,
-            } => (graph, phlo_limit, deploy, None),
+            } => (graph, None, phlo_limit, deploy, None),
         };
 
         let timestamp = deploy.timestamp;
@@ -81,6 +83,8 @@ impl AgentsTeamsService {
             contract: prepare_for_signing()
                 .code(code)
                 .valid_after_block_number(valid_after)
+                .defaults(&self.deploy_defaults)
+                .maybe_shard(shard)
                 .timestamp(timestamp)
                 .phlo_limit(phlo_limit)
                 .call(),
@@ -104,11 +108,14 @@ impl AgentsTeamsService {
             .deploy_signed_contract(request.contract)
             .await?;
 
+        let included = self.proposer.propose(deploy_id.clone());
+
         if let Some(system) = request.system {
-            write_client.deploy_signed_contract(system).await?;
+            let system_deploy_id = write_client.deploy_signed_contract(system).await?;
+            self.proposer.propose(system_deploy_id).await?;
         }
 
-        write_client.propose().await?;
+        included.await?;
         Ok(deploy_id)
     }
 }
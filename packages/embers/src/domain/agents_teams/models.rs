@@ -133,6 +133,17 @@ pub struct RunResp {
     pub contract: PreparedContract,
 }
 
+#[derive(Debug, Clone)]
+pub enum RunResult {
+    Ok {
+        cost: u64,
+        result: serde_json::Value,
+    },
+    Errored {
+        cost: u64,
+    },
+}
+
 #[derive(Debug, Clone)]
 pub struct PublishToFireskyReq {
     pub pds_url: String,
@@ -1,12 +1,13 @@
 use std::time::Duration;
 
 use anyhow::anyhow;
-use firefly_client::models::{DeployId, SignedCode, Uri};
+use firefly_client::models::{SignedCode, Uri};
+use firefly_client::node_events::DeployOutcome;
 use firefly_client::rendering::Render;
 use futures::FutureExt;
 
 use crate::domain::agents_teams::AgentsTeamsService;
-use crate::domain::agents_teams::models::{RunReq, RunResp};
+use crate::domain::agents_teams::models::{RunReq, RunResp, RunResult};
 use crate::domain::common::{prepare_for_signing, record_trace};
 
 #[derive(Debug, Clone, Render)]
@@ -16,12 +17,6 @@ struct RunAgentsTeam {
     prompt: String,
 }
 
-#[derive(Debug, Clone, Render)]
-#[template(path = "agents_teams/get_run_result.rho")]
-struct GetAgentsTeamResult {
-    deploy_id: DeployId,
-}
-
 impl AgentsTeamsService {
     #[tracing::instrument(
         level = "info",
@@ -48,6 +43,7 @@ impl AgentsTeamsService {
                 .code(contract)
                 .phlo_limit(request.phlo_limit)
                 .valid_after_block_number(valid_after)
+                .defaults(&self.deploy_defaults)
                 .call(),
         })
     }
@@ -62,23 +58,46 @@ impl AgentsTeamsService {
     pub async fn deploy_signed_run_agents_team(
         &self,
         contract: SignedCode,
-    ) -> anyhow::Result<serde_json::Value> {
+    ) -> anyhow::Result<RunResult> {
         record_trace!(contract);
 
         let mut write_client = self.write_client.clone();
 
+        // result channel is the first name the run contract creates
+        let result_channel = write_client
+            .preview_deploy_names(&contract, 1)
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("node previewed no names"))?;
+
         let deploy_id = write_client.deploy_signed_contract(contract).await?;
 
         let deploy_waiter = self
             .observer_node_events
             .wait_for_deploy(&deploy_id, Duration::from_mins(1));
-        let (_, finalized) = tokio::try_join!(write_client.propose(), deploy_waiter.map(Ok))?;
+        let (_, outcome) = tokio::try_join!(
+            self.proposer.propose(deploy_id.clone()),
+            deploy_waiter.map(Ok)
+        )?;
 
-        if !finalized {
-            return Err(anyhow!("block is not finalized"));
-        }
+        let (block_hash, cost) = match outcome {
+            DeployOutcome::Finalized {
+                errored: true,
+                cost,
+                ..
+            } => return Ok(RunResult::Errored { cost }),
+            DeployOutcome::Finalized {
+                block_hash, cost, ..
+            } => (block_hash, cost),
+            DeployOutcome::TimedOut => return Err(anyhow!("block is not finalized")),
+        };
 
-        let code = GetAgentsTeamResult { deploy_id }.render()?;
-        self.read_client.get_data(code).await.map_err(Into::into)
+        let result = write_client
+            .get_channel_value(block_hash, result_channel)
+            .await?
+            .pop()
+            .ok_or_else(|| anyhow!("agents team returned no result"))?
+            .value;
+        Ok(RunResult::Ok { cost, result })
     }
 }
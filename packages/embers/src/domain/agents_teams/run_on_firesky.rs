@@ -11,7 +11,12 @@ use atrium_xrpc_client::reqwest::ReqwestClient;
 use futures::{StreamExt, stream};
 
 use crate::domain::agents_teams::AgentsTeamsService;
-use crate::domain::agents_teams::models::{DeploySignedRunOnFireskyReq, RunReq, RunResp};
+use crate::domain::agents_teams::models::{
+    DeploySignedRunOnFireskyReq,
+    RunReq,
+    RunResp,
+    RunResult,
+};
 use crate::domain::common::upload_blob_from_url;
 
 impl AgentsTeamsService {
@@ -81,7 +86,12 @@ impl AgentsTeamsService {
             .ok_or_else(|| anyhow!("agents team is not connected to firesky"))?
             .clone();
 
-        let resp = self.deploy_signed_run_agents_team(request.contract).await?;
+        let resp = match self.deploy_signed_run_agents_team(request.contract).await? {
+            RunResult::Ok { result, .. } => result,
+            RunResult::Errored { cost } => {
+                return Err(anyhow!("agents team run errored, cost: {cost}"));
+            }
+        };
 
         let http_client = ReqwestClient::new(cred.pds_url);
         let session = CredentialSession::new(http_client, MemorySessionStore::default());
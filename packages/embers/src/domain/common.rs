@@ -4,8 +4,7 @@ use atrium_api::agent::Agent;
 use atrium_api::types::BlobRef;
 use chrono::{DateTime, Utc};
 use firefly_client::helpers::ShortHex;
-use firefly_client::models::casper::DeployDataProto;
-use prost::Message;
+use firefly_signing::UnsignedDeploy;
 use secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
 
@@ -24,26 +23,34 @@ macro_rules! record_trace {
 
 pub(crate) use record_trace;
 
+/// Network wide deploy parameters used unless a request overrides them.
+#[derive(Debug, Clone)]
+pub struct DeployDefaults {
+    pub shard_id: String,
+    pub phlo_price: i64,
+}
+
 #[bon::builder]
 pub fn prepare_for_signing(
     code: String,
     valid_after_block_number: u64,
+    defaults: &DeployDefaults,
+    shard: Option<String>,
     phlo_limit: Option<PositiveNonZero<i64>>,
     timestamp: Option<DateTime<Utc>>,
 ) -> PreparedContract {
     let timestamp = timestamp
         .unwrap_or_else(chrono::Utc::now)
         .timestamp_millis();
-    let contract = DeployDataProto {
+    let contract = UnsignedDeploy {
         term: code,
         timestamp,
-        phlo_price: 1,
+        phlo_price: defaults.phlo_price,
         phlo_limit: phlo_limit.map_or(5_000_000, |v| v.0),
         valid_after_block_number: valid_after_block_number as _,
-        shard_id: "root".into(),
-        ..Default::default()
+        shard_id: shard.unwrap_or_else(|| defaults.shard_id.clone()),
     }
-    .encode_to_vec();
+    .encode();
 
     PreparedContract(contract)
 }
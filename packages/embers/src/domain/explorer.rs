@@ -0,0 +1,69 @@
+use firefly_client::BlockExplorer;
+use firefly_client::block_explorer::{Block, BlockSummary};
+use firefly_client::models::BlockId;
+use futures::TryStreamExt;
+
+use crate::domain::common::record_trace;
+
+/// Longest range of heights listed at once.
+pub const MAX_HEIGHTS: u32 = 100;
+
+#[derive(Clone)]
+pub struct ExplorerService {
+    pub explorer: BlockExplorer,
+}
+
+impl ExplorerService {
+    #[tracing::instrument(
+        level = "info",
+        skip_all,
+        fields(hash),
+        err(Debug),
+        ret(Debug, level = "trace")
+    )]
+    pub async fn get_block(&self, hash: BlockId) -> anyhow::Result<Block> {
+        record_trace!(hash);
+
+        self.explorer.block(&hash).await.map_err(Into::into)
+    }
+
+    /// Blocks with numbers from `from` up to `to`, cut to [`MAX_HEIGHTS`].
+    #[tracing::instrument(
+        level = "info",
+        skip_all,
+        fields(from, to),
+        err(Debug),
+        ret(Debug, level = "trace")
+    )]
+    pub async fn list_blocks(&self, from: u64, to: u64) -> anyhow::Result<Vec<Block>> {
+        record_trace!(from, to);
+
+        let to = to.min(from.saturating_add(u64::from(MAX_HEIGHTS) - 1));
+        if to < from {
+            return Ok(vec![]);
+        }
+
+        self.explorer
+            .blocks_by_heights(from..=to)
+            .try_collect()
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Latest `depth` blocks of the main chain, cut to [`MAX_HEIGHTS`].
+    #[tracing::instrument(
+        level = "info",
+        skip_all,
+        fields(depth),
+        err(Debug),
+        ret(Debug, level = "trace")
+    )]
+    pub async fn get_main_chain(&self, depth: u32) -> anyhow::Result<Vec<BlockSummary>> {
+        record_trace!(depth);
+
+        self.explorer
+            .main_chain(depth.min(MAX_HEIGHTS))
+            .await
+            .map_err(Into::into)
+    }
+}
@@ -2,9 +2,11 @@ use anyhow::Context;
 use firefly_client::helpers::insert_signed_signature;
 use firefly_client::models::{DeployData, Uri};
 use firefly_client::rendering::Render;
-use firefly_client::{NodeEvents, ReadNodeClient, WriteNodeClient};
+use firefly_client::{NodeEvents, Proposer, ReadNodeClient, WriteNodeClient};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 
+use crate::domain::common::DeployDefaults;
+
 mod create_test_wallet;
 mod deploy_test;
 pub mod models;
@@ -12,8 +14,10 @@ pub mod models;
 #[derive(Clone)]
 pub struct TestnetService {
     pub uri: Uri,
+    pub deploy_defaults: DeployDefaults,
     pub service_key: SecretKey,
     pub write_client: WriteNodeClient,
+    pub proposer: Proposer,
     pub read_client: ReadNodeClient,
     pub observer_node_events: NodeEvents,
 }
@@ -33,10 +37,12 @@ impl TestnetService {
     #[tracing::instrument(level = "info", skip_all, err(Debug))]
     pub async fn bootstrap(
         mut write_client: WriteNodeClient,
+        proposer: Proposer,
         read_client: ReadNodeClient,
         observer_node_events: NodeEvents,
         deployer_key: SecretKey,
         env_key: &SecretKey,
+        deploy_defaults: DeployDefaults,
     ) -> anyhow::Result<Self> {
         let secp = Secp256k1::new();
         let env_public_key = PublicKey::from_secret_key(&secp, env_key);
@@ -57,7 +63,11 @@ impl TestnetService {
 
         tracing::debug!("code = {code}");
 
-        let deploy_data = DeployData::builder(code).timestamp(timestamp).build();
+        let deploy_data = DeployData::builder(code)
+            .timestamp(timestamp)
+            .phlo_price(deploy_defaults.phlo_price)
+            .shard_id(deploy_defaults.shard_id.clone())
+            .build();
 
         write_client
             .deploy(&deployer_key, deploy_data)
@@ -66,8 +76,10 @@ impl TestnetService {
 
         Ok(Self {
             uri: env_uri,
+            deploy_defaults,
             service_key: deployer_key,
             write_client,
+            proposer,
             read_client,
             observer_node_events,
         })
@@ -29,11 +29,13 @@ impl TestnetService {
             amount: TEST_WALLET_BALANCE,
         }
         .builder()?
+        .phlo_price(self.deploy_defaults.phlo_price)
+        .shard_id(self.deploy_defaults.shard_id.clone())
         .build();
 
         let mut write_client = self.write_client.clone();
-        write_client.deploy(&self.service_key, deploy_data).await?;
-        write_client.propose().await?;
+        let deploy_id = write_client.deploy(&self.service_key, deploy_data).await?;
+        self.proposer.propose(deploy_id).await?;
 
         Ok(CreateTestwalletResp {
             key: test_account_secret_key,
@@ -2,6 +2,7 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use firefly_client::models::{DeployId, Uri};
+use firefly_client::node_events::DeployOutcome;
 use firefly_client::rendering::Render;
 
 use crate::blockchain::testnet::models;
@@ -41,11 +42,13 @@ impl TestnetService {
                 prepare_for_signing()
                     .code(env)
                     .valid_after_block_number(valid_after)
+                    .defaults(&self.deploy_defaults)
                     .call()
             }),
             test_contract: prepare_for_signing()
                 .code(request.test)
                 .valid_after_block_number(valid_after)
+                .defaults(&self.deploy_defaults)
                 .call(),
         })
     }
@@ -67,13 +70,16 @@ impl TestnetService {
 
         if let Some(contract) = request.env {
             let result = write_client.deploy_signed_contract(contract).await;
-            if let Err(err) = result {
-                return Ok(DeploySignedTestResp::EnvDeployFailed {
-                    error: err.to_string(),
-                });
-            }
+            let env_deploy_id = match result {
+                Ok(deploy_id) => deploy_id,
+                Err(err) => {
+                    return Ok(DeploySignedTestResp::EnvDeployFailed {
+                        error: err.to_string(),
+                    });
+                }
+            };
 
-            write_client.propose().await?;
+            self.proposer.propose(env_deploy_id).await?;
         }
 
         let result = write_client.deploy_signed_contract(request.test).await;
@@ -89,12 +95,13 @@ impl TestnetService {
         let deploy_waiter = self
             .observer_node_events
             .wait_for_deploy(&deploy_id, Duration::from_mins(1));
-        let (_, finalized) =
-            tokio::try_join!(write_client.propose(), async { Ok(deploy_waiter.await) })?;
+        let (_, outcome) = tokio::try_join!(self.proposer.propose(deploy_id.clone()), async {
+            Ok(deploy_waiter.await)
+        })?;
 
-        if !finalized {
+        let DeployOutcome::Finalized { cost, errored, .. } = outcome else {
             return Err(anyhow!("block is not finalized"));
-        }
+        };
 
         let code = GetLogs {
             deploy_id,
@@ -103,13 +110,16 @@ impl TestnetService {
         .render()?;
 
         let logs: Option<Vec<models::Log>> = self.read_client.get_data(code).await?;
+        let logs = logs
+            .unwrap_or_default()
+            .into_iter()
+            .map(Into::into)
+            .collect();
 
-        Ok(DeploySignedTestResp::Ok {
-            logs: logs
-                .unwrap_or_default()
-                .into_iter()
-                .map(Into::into)
-                .collect(),
-        })
+        if errored {
+            Ok(DeploySignedTestResp::TestDeployErrored { cost, logs })
+        } else {
+            Ok(DeploySignedTestResp::Ok { cost, logs })
+        }
     }
 }
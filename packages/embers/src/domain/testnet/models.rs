@@ -43,5 +43,6 @@ pub struct Log {
 pub enum DeploySignedTestResp {
     EnvDeployFailed { error: String },
     TestDeployFailed { error: String },
-    Ok { logs: Vec<Log> },
+    TestDeployErrored { cost: u64, logs: Vec<Log> },
+    Ok { cost: u64, logs: Vec<Log> },
 }
@@ -2,9 +2,11 @@ use anyhow::Context;
 use firefly_client::helpers::insert_signed_signature;
 use firefly_client::models::{DeployData, Uri};
 use firefly_client::rendering::Render;
-use firefly_client::{NodeEvents, ReadNodeClient, WriteNodeClient};
+use firefly_client::{NodeEvents, Proposer, ReadNodeClient, WriteNodeClient};
 use secp256k1::{PublicKey, Secp256k1, SecretKey};
 
+use crate::domain::common::DeployDefaults;
+
 mod boost;
 mod get_wallet_state_and_history;
 pub mod models;
@@ -14,7 +16,9 @@ mod transfer;
 #[derive(Clone)]
 pub struct WalletsService {
     pub uri: Uri,
+    pub deploy_defaults: DeployDefaults,
     pub write_client: WriteNodeClient,
+    pub proposer: Proposer,
     pub read_client: ReadNodeClient,
     pub validator_node_events: NodeEvents,
     pub observer_node_events: NodeEvents,
@@ -32,14 +36,17 @@ struct InitWalletsEnv {
 
 #[allow(unused)]
 impl WalletsService {
+    #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(level = "info", skip_all, err(Debug))]
     pub async fn bootstrap(
         mut write_client: WriteNodeClient,
+        proposer: Proposer,
         read_client: ReadNodeClient,
         validator_node_events: NodeEvents,
         observer_node_events: NodeEvents,
         deployer_key: &SecretKey,
         env_key: &SecretKey,
+        deploy_defaults: DeployDefaults,
     ) -> anyhow::Result<Self> {
         let secp = Secp256k1::new();
         let env_public_key = PublicKey::from_secret_key(&secp, env_key);
@@ -60,7 +67,11 @@ impl WalletsService {
 
         tracing::debug!("code = {code}");
 
-        let deploy_data = DeployData::builder(code).timestamp(timestamp).build();
+        let deploy_data = DeployData::builder(code)
+            .timestamp(timestamp)
+            .phlo_price(deploy_defaults.phlo_price)
+            .shard_id(deploy_defaults.shard_id.clone())
+            .build();
 
         write_client
             .deploy(deployer_key, deploy_data)
@@ -69,7 +80,9 @@ impl WalletsService {
 
         Ok(Self {
             uri: env_uri,
+            deploy_defaults,
             write_client,
+            proposer,
             read_client,
             validator_node_events,
             observer_node_events,
@@ -45,6 +45,7 @@ impl WalletsService {
         let contract = prepare_for_signing()
             .code(contract)
             .valid_after_block_number(valid_after)
+            .defaults(&self.deploy_defaults)
             .call();
         Ok(TransferResp { contract })
     }
@@ -62,7 +63,7 @@ impl WalletsService {
         let mut write_client = self.write_client.clone();
 
         let deploy_id = write_client.deploy_signed_contract(contract).await?;
-        write_client.propose().await?;
+        self.proposer.propose(deploy_id.clone()).await?;
         Ok(deploy_id)
     }
 }
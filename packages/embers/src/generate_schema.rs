@@ -2,6 +2,7 @@ use poem_openapi::OpenApiService;
 
 use crate::api::agents::AgentsApi;
 use crate::api::agents_teams::AgentsTeamsApi;
+use crate::api::explorer::ExplorerApi;
 use crate::api::oslfs::OslfsApi;
 use crate::api::service::ServiceApi;
 use crate::api::testnet::TestnetApi;
@@ -21,6 +22,7 @@ async fn main() -> std::io::Result<()> {
             AgentsApi,
             AgentsTeamsApi,
             OslfsApi,
+            ExplorerApi,
         ),
         "Embers API",
         "0.1.0",
@@ -0,0 +1,198 @@
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry as MapEntry;
+use poem::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use poem::{Endpoint, IntoResponse, Middleware, Request, Response};
+use tokio::sync::OnceCell;
+
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+const IDEMPOTENT_REPLAYED: &str = "idempotent-replayed";
+
+/// Responses by request path and idempotency key.
+type Responses = DashMap<(String, [u8; 32]), Arc<Entry>>;
+
+/// Deduplicates `*/send` requests, so retried submission gets the original response
+/// instead of deploying signed contract again.
+///
+/// Requests are identified by `Idempotency-Key` header or, without it,
+/// by hash of the signed contracts they carry. A known `Idempotency-Key` with other contracts
+/// is rejected with `422`, so clients that happen to share a key never get each other's deploys.
+///
+/// Responses are kept in process memory only, replicas don't see each other's entries.
+#[derive(Clone)]
+pub struct IdempotentSend {
+    ttl: Duration,
+    responses: Arc<Responses>,
+}
+
+impl IdempotentSend {
+    /// Must be called within tokio runtime, expired responses are evicted by a background task.
+    pub fn new(ttl: Duration) -> Self {
+        let responses = Arc::<Responses>::default();
+        tokio::spawn(evict_expired(Arc::downgrade(&responses), ttl));
+        Self { ttl, responses }
+    }
+}
+
+/// Periodically drops expired responses, stops once the middleware is dropped.
+async fn evict_expired(responses: Weak<Responses>, ttl: Duration) {
+    let mut interval = tokio::time::interval(ttl.max(Duration::from_secs(1)));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let Some(responses) = responses.upgrade() else {
+            return;
+        };
+        responses.retain(|_, entry| !entry.is_expired(ttl));
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for IdempotentSend {
+    type Output = IdempotentSendEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        IdempotentSendEndpoint {
+            ep,
+            state: self.clone(),
+        }
+    }
+}
+
+pub struct IdempotentSendEndpoint<E> {
+    ep: E,
+    state: IdempotentSend,
+}
+
+struct Entry {
+    created_at: Instant,
+    /// Hash of the signed contracts the response was made for.
+    contracts: [u8; 32],
+    response: OnceCell<CachedResponse>,
+}
+
+impl Entry {
+    fn new(contracts: [u8; 32]) -> Arc<Self> {
+        Arc::new(Self {
+            created_at: Instant::now(),
+            contracts,
+            response: OnceCell::new(),
+        })
+    }
+
+    fn is_expired(&self, ttl: Duration) -> bool {
+        self.created_at.elapsed() >= ttl
+    }
+}
+
+#[derive(Clone)]
+struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl CachedResponse {
+    fn to_response(&self) -> Response {
+        let mut response = Response::builder()
+            .status(self.status)
+            .body(self.body.clone());
+        response.headers_mut().clone_from(&self.headers);
+        response
+    }
+}
+
+impl<E: Endpoint> Endpoint for IdempotentSendEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, mut req: Request) -> poem::Result<Self::Output> {
+        if req.method() != Method::POST || !req.uri().path().ends_with("/send") {
+            return self.ep.call(req).await.map(IntoResponse::into_response);
+        }
+
+        let body = req.take_body().into_bytes().await?;
+        let contracts = contracts_hash(&body);
+        let key = (
+            req.uri().path().to_owned(),
+            idempotency_key(req.headers()).unwrap_or(contracts),
+        );
+        req.set_body(body);
+
+        let entry = match self.state.responses.entry(key) {
+            MapEntry::Occupied(mut occupied) => {
+                if occupied.get().is_expired(self.state.ttl) {
+                    occupied.insert(Entry::new(contracts));
+                }
+                occupied.get().clone()
+            }
+            MapEntry::Vacant(vacant) => vacant.insert(Entry::new(contracts)).clone(),
+        };
+
+        if entry.contracts != contracts {
+            return Err(poem::Error::from_string(
+                "Idempotency-Key was already used with other contracts",
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ));
+        }
+
+        let mut replayed = true;
+        let first_call = &mut replayed;
+        let cached = entry
+            .response
+            .get_or_try_init(|| async move {
+                *first_call = false;
+                let response = self.ep.call(req).await.map_err(Err)?.into_response();
+                if !response.status().is_success() {
+                    return Err(Ok(response));
+                }
+
+                let (parts, body) = response.into_parts();
+                Ok(CachedResponse {
+                    status: parts.status,
+                    headers: parts.headers,
+                    body: body.into_vec().await.map_err(|err| Err(err.into()))?,
+                })
+            })
+            .await;
+
+        match cached {
+            Ok(cached) => {
+                let mut response = cached.to_response();
+                if replayed {
+                    response
+                        .headers_mut()
+                        .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+                }
+                Ok(response)
+            }
+            Err(response) => response,
+        }
+    }
+}
+
+fn idempotency_key(headers: &HeaderMap) -> Option<[u8; 32]> {
+    headers.get(IDEMPOTENCY_KEY).map(|key| {
+        Blake2b::<U32>::new()
+            .chain_update(b"header:")
+            .chain_update(key.as_bytes())
+            .finalize()
+            .into()
+    })
+}
+
+fn contracts_hash(body: &[u8]) -> [u8; 32] {
+    // send bodies carry signed contracts in `request`
+    let contracts = serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|mut body| body.get_mut("request").map(serde_json::Value::take))
+        .map_or_else(|| body.to_vec(), |request| request.to_string().into_bytes());
+
+    Blake2b::<U32>::new()
+        .chain_update(b"contracts:")
+        .chain_update(contracts)
+        .finalize()
+        .into()
+}
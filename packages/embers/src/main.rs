@@ -1,30 +1,43 @@
+use std::time::Duration;
+
 use anyhow::Context;
-use firefly_client::{NodeEvents, ReadNodeClient, WriteNodeClient};
+use firefly_client::errors::{ProposerError, ServiceError, WriteNodeError};
+use firefly_client::proposer::ProposerConfig;
+use firefly_client::{BlockExplorer, NodeEvents, Proposer, ReadNodeClient, WriteNodeClient};
+use poem::http::StatusCode;
 use poem::listener::TcpListener;
 use poem::middleware::{Compression, Cors, NormalizePath, RequestId, Tracing, TrailingSlash};
-use poem::{EndpointExt, Route, Server};
+use poem::{Endpoint, EndpointExt, Route, Server};
 use poem_openapi::OpenApiService;
-use secp256k1::rand;
-use secp256k1::rand::distr::{Alphanumeric, SampleString};
 use tokio::try_join;
 
 use crate::api::agents::AgentsApi;
 use crate::api::agents_teams::AgentsTeamsApi;
+use crate::api::common::TokenKeys;
+use crate::api::explorer::ExplorerApi;
 use crate::api::oslfs::OslfsApi;
 use crate::api::service::ServiceApi;
 use crate::api::testnet::TestnetApi;
 use crate::api::wallets::WalletsApi;
-use crate::configuration::collect_config;
+use crate::configuration::{Jwt, collect_config};
 use crate::domain::agents::AgentsService;
 use crate::domain::agents_teams::AgentsTeamsService;
+use crate::domain::common::DeployDefaults;
+use crate::domain::explorer::ExplorerService;
 use crate::domain::oslfs::OslfsService;
 use crate::domain::testnet::TestnetService;
 use crate::domain::wallets::WalletsService;
+use crate::idempotency::IdempotentSend;
 
 mod api;
 mod blockchain;
 mod configuration;
 mod domain;
+mod idempotency;
+#[cfg(test)]
+mod tests;
+
+const HEALTH_CHECK_PERIOD: Duration = Duration::from_secs(10);
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -42,91 +55,165 @@ async fn main() -> anyhow::Result<()> {
         )
         .init();
 
-    let read_client = ReadNodeClient::new(config.mainnet.observer_url);
-    let validator_node_events = NodeEvents::new(&config.mainnet.validator_ws_api_url);
-    let observer_node_events = NodeEvents::new(&config.mainnet.observer_ws_api_url);
+    let read_client = read_node_client(
+        config.mainnet.observer_url,
+        config.mainnet.observer_grpc_url,
+    )
+    .await?;
+
+    let testnet_read_client = read_node_client(
+        config.testnet.observer_url,
+        config.testnet.observer_grpc_url,
+    )
+    .await?;
 
-    let testnet_read_client = ReadNodeClient::new(config.testnet.observer_url);
-    let _testnet_validator_node_events = NodeEvents::new(&config.testnet.validator_ws_api_url);
-    let testnet_observer_node_events = NodeEvents::new(&config.testnet.observer_ws_api_url);
+    let mainnet_deploy_defaults = DeployDefaults {
+        shard_id: config.mainnet.shard_id,
+        phlo_price: config.mainnet.phlo_price,
+    };
 
-    let ((agents_service, agents_teams_service, oslfs_service, wallets_service), testnet_service) =
+    let (
+        (agents_service, agents_teams_service, oslfs_service, wallets_service, explorer_service),
+        testnet_service,
+    ) =
         try_join!(
             async {
-                let mut write_client = WriteNodeClient::new(
-                    config.mainnet.deploy_service_url,
-                    config.mainnet.propose_service_url,
+                let write_client = WriteNodeClient::with_validators(
+                    std::iter::once((
+                        config.mainnet.deploy_service_url,
+                        config.mainnet.propose_service_url,
+                    ))
+                    .chain(config.mainnet.extra_validators.into_iter().map(
+                        |validator| (validator.deploy_service_url, validator.propose_service_url),
+                    )),
                 )
                 .await?;
+                write_client.spawn_health_checks(HEALTH_CHECK_PERIOD);
+                let proposer = Proposer::start(
+                    write_client.clone(),
+                    ProposerConfig::builder()
+                        .interval(Duration::from_millis(config.mainnet.propose_interval_ms))
+                        .max_batch(config.mainnet.propose_batch_size)
+                        .build(),
+                );
+
+                let validator_node_events = NodeEvents::with_backfill(
+                    &config.mainnet.validator_ws_api_url,
+                    write_client.clone(),
+                );
+                let observer_node_events = NodeEvents::with_backfill(
+                    &config.mainnet.observer_ws_api_url,
+                    write_client.clone(),
+                );
 
                 let agents_service = AgentsService::bootstrap(
                     write_client.clone(),
+                    proposer.clone(),
                     read_client.clone(),
                     &config.mainnet.service_key,
                     &config.mainnet.agents_env_key,
+                    mainnet_deploy_defaults.clone(),
                 )
                 .await?;
 
                 let agents_teams_service = AgentsTeamsService::bootstrap(
                     write_client.clone(),
+                    proposer.clone(),
                     read_client.clone(),
                     observer_node_events.clone(),
                     &config.mainnet.service_key,
                     &config.mainnet.agents_teams_env_key,
+                    mainnet_deploy_defaults.clone(),
                     config.aes_encryption_key.into(),
                 )
                 .await?;
 
                 let oslfs_service = OslfsService::bootstrap(
                     write_client.clone(),
+                    proposer.clone(),
                     read_client.clone(),
                     &config.mainnet.service_key,
                     &config.mainnet.oslfs_env_key,
+                    mainnet_deploy_defaults.clone(),
                 )
                 .await?;
 
                 let wallets_service = WalletsService::bootstrap(
                     write_client.clone(),
+                    proposer.clone(),
                     read_client,
                     validator_node_events,
                     observer_node_events,
                     &config.mainnet.service_key,
                     &config.mainnet.wallets_env_key,
+                    mainnet_deploy_defaults,
                 )
                 .await?;
 
-                write_client.propose().await?;
+                write_client.propose_deployed().await?;
+
+                let explorer_service = ExplorerService {
+                    explorer: BlockExplorer::new(write_client),
+                };
 
                 anyhow::Ok((
                     agents_service,
                     agents_teams_service,
                     oslfs_service,
                     wallets_service,
+                    explorer_service,
                 ))
             },
             async {
-                let mut testnet_write_client = WriteNodeClient::new(
-                    config.testnet.deploy_service_url,
-                    config.testnet.propose_service_url,
+                let testnet_write_client = WriteNodeClient::with_validators(
+                    std::iter::once((
+                        config.testnet.deploy_service_url,
+                        config.testnet.propose_service_url,
+                    ))
+                    .chain(config.testnet.extra_validators.into_iter().map(
+                        |validator| (validator.deploy_service_url, validator.propose_service_url),
+                    )),
                 )
                 .await?;
+                testnet_write_client.spawn_health_checks(HEALTH_CHECK_PERIOD);
+                let testnet_proposer = Proposer::start(
+                    testnet_write_client.clone(),
+                    ProposerConfig::builder()
+                        .interval(Duration::from_millis(config.testnet.propose_interval_ms))
+                        .max_batch(config.testnet.propose_batch_size)
+                        .build(),
+                );
+
+                let _testnet_validator_node_events = NodeEvents::with_backfill(
+                    &config.testnet.validator_ws_api_url,
+                    testnet_write_client.clone(),
+                );
+                let testnet_observer_node_events = NodeEvents::with_backfill(
+                    &config.testnet.observer_ws_api_url,
+                    testnet_write_client.clone(),
+                );
 
                 let testnet_service = TestnetService::bootstrap(
                     testnet_write_client.clone(),
+                    testnet_proposer,
                     testnet_read_client,
                     testnet_observer_node_events,
                     config.testnet.service_key,
                     &config.testnet.env_key,
+                    DeployDefaults {
+                        shard_id: config.testnet.shard_id,
+                        phlo_price: config.testnet.phlo_price,
+                    },
                 )
                 .await?;
 
-                testnet_write_client.propose().await?;
+                testnet_write_client.propose_deployed().await?;
 
                 anyhow::Ok(testnet_service)
             },
         )?;
 
-    let secret = Alphanumeric.sample_string(&mut rand::rng(), 20);
+    let token_keys = token_keys(config.jwt)?;
 
     let api = OpenApiService::new(
         (
@@ -136,6 +223,7 @@ async fn main() -> anyhow::Result<()> {
             AgentsApi,
             AgentsTeamsApi,
             OslfsApi,
+            ExplorerApi,
         ),
         "Embers API",
         "0.1.0",
@@ -151,13 +239,19 @@ async fn main() -> anyhow::Result<()> {
         .nest("/swagger-ui/index.html", ui)
         .nest("/swagger-ui/openapi.json", spec)
         .nest("/swagger-ui/openapi.yaml", spec_yaml)
-        .data(jsonwebtoken::EncodingKey::from_secret(secret.as_ref()))
-        .data(jsonwebtoken::DecodingKey::from_secret(secret.as_ref()))
+        .data(token_keys)
         .data(agents_service)
         .data(agents_teams_service)
         .data(oslfs_service)
         .data(wallets_service)
         .data(testnet_service)
+        .data(explorer_service)
+        .around(|endpoint, request| async move {
+            endpoint.call(request).await.map_err(map_write_node_error)
+        })
+        .with(IdempotentSend::new(Duration::from_secs(
+            config.idempotency_ttl_secs,
+        )))
         .with(Cors::new().allow_origin_regex("*"))
         .with(RequestId::default())
         .with(Tracing)
@@ -178,3 +272,68 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Prefers grpc transport when configured.
+async fn read_node_client(
+    observer_url: String,
+    observer_grpc_url: Option<String>,
+) -> anyhow::Result<ReadNodeClient> {
+    match observer_grpc_url {
+        Some(url) => ReadNodeClient::new_grpc(url).await,
+        None => Ok(ReadNodeClient::new(observer_url)),
+    }
+}
+
+/// Replaces generic 500 for node failures with a status
+/// that tells client whether request should be fixed or retried.
+fn map_write_node_error(err: poem::Error) -> poem::Error {
+    if err.downcast_ref::<ProposerError>().is_some() {
+        return poem::Error::from_string(err.to_string(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    let Some(write_node_error) = err.downcast_ref::<WriteNodeError>() else {
+        return err;
+    };
+
+    let status = match write_node_error {
+        WriteNodeError::Service(
+            ServiceError::InsufficientPhlo(_)
+            | ServiceError::InvalidSignature(_)
+            | ServiceError::Expired(_),
+        ) => StatusCode::UNPROCESSABLE_ENTITY,
+        WriteNodeError::Service(ServiceError::DuplicateDeploy(_)) => StatusCode::CONFLICT,
+        WriteNodeError::Service(ServiceError::DeployNotFound(_)) => StatusCode::NOT_FOUND,
+        WriteNodeError::InvalidContract(_) | WriteNodeError::InvalidDeployId(_) => {
+            StatusCode::BAD_REQUEST
+        }
+        WriteNodeError::ProposeRefused(_) => StatusCode::SERVICE_UNAVAILABLE,
+        WriteNodeError::Connection(_)
+        | WriteNodeError::Transport(_)
+        | WriteNodeError::NoValidators
+        | WriteNodeError::Service(ServiceError::Other(_))
+        | WriteNodeError::ResponseFormat(_)
+        | WriteNodeError::Decoding(_) => StatusCode::BAD_GATEWAY,
+    };
+
+    poem::Error::from_string(err.to_string(), status)
+}
+
+/// Signs new tokens with `key_id` key and accepts tokens signed with any configured key.
+fn token_keys(jwt: Jwt) -> anyhow::Result<TokenKeys> {
+    let signing_key = jwt
+        .keys
+        .get(&jwt.key_id)
+        .map(|secret| jsonwebtoken::EncodingKey::from_secret(&secret.0))
+        .with_context(|| format!("jwt key {} is not among jwt keys", jwt.key_id))?;
+
+    Ok(TokenKeys {
+        signing_key_id: jwt.key_id,
+        signing_key,
+        verification_keys: jwt
+            .keys
+            .into_iter()
+            .map(|(id, secret)| (id, jsonwebtoken::DecodingKey::from_secret(&secret.0)))
+            .collect(),
+        ttl: jwt.token_ttl,
+    })
+}
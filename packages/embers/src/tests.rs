@@ -0,0 +1,162 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use anyhow::Context;
+use firefly_client::errors::{ProposerError, ServiceError, SignedContractError, WriteNodeError};
+use firefly_client::fake_node::FakeNode;
+use firefly_client::models::{DeployId, NodeEventKind, SignedCode, WalletAddress};
+use firefly_client::node_events::{BlockFilter, DeployOutcome};
+use firefly_client::proposer::ProposerConfig;
+use firefly_client::{NodeEvents, Proposer};
+use futures::StreamExt;
+use poem::http::{Method, StatusCode};
+use poem::{Endpoint, EndpointExt, Request};
+use secp256k1::{Secp256k1, SecretKey};
+
+use crate::api::common::SendRequestError;
+use crate::domain::common::DeployDefaults;
+use crate::domain::wallets::WalletsService;
+use crate::domain::wallets::models::TransferReq;
+use crate::idempotency::IdempotentSend;
+use crate::map_write_node_error;
+
+#[test]
+fn test_node_errors_keep_their_status_through_anyhow() {
+    let err: anyhow::Error = WriteNodeError::Service(ServiceError::InvalidSignature(
+        "Invalid signature".to_owned(),
+    ))
+    .into();
+    let err = poem::Error::from(err.context("failed to deploy"));
+    assert_eq!(
+        map_write_node_error(err).status(),
+        StatusCode::UNPROCESSABLE_ENTITY
+    );
+
+    let err = Err::<(), _>(ProposerError::Stopped).context("failed to propose");
+    let err = poem::Error::from(err.unwrap_err());
+    assert_eq!(
+        map_write_node_error(err).status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+#[tokio::test]
+async fn test_idempotency_key_is_scoped_to_contracts() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let endpoint = poem::endpoint::make({
+        let calls = calls.clone();
+        move |_| {
+            let calls = calls.clone();
+            async move { calls.fetch_add(1, Ordering::SeqCst).to_string() }
+        }
+    })
+    .with(IdempotentSend::new(Duration::from_mins(1)));
+
+    let send = |contracts: &'static str| {
+        Request::builder()
+            .method(Method::POST)
+            .uri_str("/api/agents/deploy/send")
+            .header("Idempotency-Key", "key")
+            .body(format!(r#"{{"request": "{contracts}"}}"#))
+    };
+
+    let first = endpoint.call(send("a")).await.unwrap();
+    assert!(!first.headers().contains_key("idempotent-replayed"));
+    assert_eq!(first.into_body().into_string().await.unwrap(), "0");
+
+    let replayed = endpoint.call(send("a")).await.unwrap();
+    assert_eq!(replayed.headers()["idempotent-replayed"], "true");
+    assert_eq!(replayed.into_body().into_string().await.unwrap(), "0");
+
+    let err = endpoint.call(send("b")).await.unwrap_err();
+    assert_eq!(err.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_send_rejection_has_reason_code() {
+    let err = poem::Error::from(SendRequestError::from(
+        SignedContractError::InvalidSignature,
+    ));
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+
+    let body: serde_json::Value =
+        serde_json::from_slice(&err.into_response().into_body().into_bytes().await.unwrap())
+            .unwrap();
+    assert_eq!(body["reason"], "invalid_signature");
+}
+
+#[tokio::test]
+async fn test_transfer_is_prepared_sent_and_finalized() {
+    let node = FakeNode::start().await.unwrap();
+    let write_client = node.write_client().await.unwrap();
+    let proposer = Proposer::start(
+        write_client.clone(),
+        ProposerConfig::builder()
+            .interval(Duration::from_millis(50))
+            .build(),
+    );
+    let events = NodeEvents::new(&node.ws_url());
+    let mut started = events.subscribe_blocks(
+        BlockFilter::builder()
+            .kinds([NodeEventKind::Started])
+            .build(),
+    );
+
+    let service_key = SecretKey::from_byte_array([1; 32]).unwrap();
+    let wallets = WalletsService::bootstrap(
+        write_client.clone(),
+        proposer,
+        node.read_client_http(),
+        events.clone(),
+        events.clone(),
+        &service_key,
+        &SecretKey::from_byte_array([2; 32]).unwrap(),
+        DeployDefaults {
+            shard_id: "root".into(),
+            phlo_price: 1,
+        },
+    )
+    .await
+    .unwrap();
+    write_client.propose_deployed().await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), started.next())
+        .await
+        .unwrap();
+
+    let key = SecretKey::from_byte_array([3; 32]).unwrap();
+    let from = WalletAddress::from(key.public_key(&Secp256k1::new()));
+    let prepared = wallets
+        .prepare_transfer_contract(TransferReq {
+            from: from.clone(),
+            to: from,
+            amount: 10.try_into().unwrap(),
+            description: None,
+        })
+        .await
+        .unwrap()
+        .contract
+        .0;
+
+    // proposer may finalize the deploy before send returns, so the wait starts first
+    let sig = firefly_signing::sign_contract(&key, &prepared);
+    let deploy_id = DeployId::from(hex::encode(&sig));
+    let outcome = events.wait_for_deploy(&deploy_id, Duration::from_secs(10));
+
+    let sent = wallets
+        .deploy_signed_transfer(SignedCode {
+            sig,
+            sig_algorithm: firefly_signing::SIG_ALGORITHM.into(),
+            deployer: firefly_signing::deployer(&key).into(),
+            contract: prepared,
+        })
+        .await
+        .unwrap();
+    assert_eq!(sent, deploy_id);
+
+    assert!(matches!(
+        outcome.await,
+        DeployOutcome::Finalized { errored: false, .. }
+    ));
+}
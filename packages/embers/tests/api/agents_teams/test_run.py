@@ -23,3 +23,23 @@ def test_run(client: ApiClient, funded_wallet: Wallet, graph: str):
     agents_team = public_key_to_uri(private_key.public_key)
     resp = client.agents_teams.run(funded_wallet, "echo", phlo_limit=5_000_000, agents_team=agents_team)
     assert resp.json == "echo"
+
+
+@pytest.mark.parametrize("funded_wallet", [100_000_000], indirect=True)
+@pytest.mark.parametrize("graph", [ECHO_TEAM])
+def test_run__each_run_reads_own_result(client: ApiClient, funded_wallet: Wallet, graph: str):
+    # runs read answers from the name the node previews, so this pins its name derivation order
+    private_key = SECP256k1.generate()
+    deploy = insert_signed_deploy(private_key, datetime.now(UTC), funded_wallet, version=0)
+
+    client.agents_teams.deploy_graph(
+        funded_wallet,
+        graph=graph,
+        phlo_limit=5_000_000,
+        deploy=deploy,
+    )
+
+    agents_team = public_key_to_uri(private_key.public_key)
+    for prompt in ["first", "second"]:
+        resp = client.agents_teams.run(funded_wallet, prompt, phlo_limit=5_000_000, agents_team=agents_team)
+        assert resp.json == prompt
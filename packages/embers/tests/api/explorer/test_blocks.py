@@ -0,0 +1,26 @@
+from tests.client import ApiClient
+
+
+def test_main_chain(client: ApiClient):
+    resp = client.explorer.main_chain(depth=2)
+
+    assert resp.status == 200
+    assert 0 < len(resp.json) <= 2
+    assert int(resp.json[0]["number"]) >= int(resp.json[-1]["number"])
+
+
+def test_list_blocks(client: ApiClient):
+    resp = client.explorer.list_blocks(0, 1)
+
+    assert resp.status == 200
+    assert [int(block["summary"]["number"]) for block in resp.json] == [0, 1]
+
+
+def test_get_block(client: ApiClient):
+    head = client.explorer.main_chain(depth=1).json[0]
+
+    resp = client.explorer.get_block(head["hash"])
+
+    assert resp.status == 200
+    assert resp.json["summary"]["hash"] == head["hash"]
+    assert len(resp.json["deploys"]) == head["deploy_count"]
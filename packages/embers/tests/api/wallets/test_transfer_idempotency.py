@@ -0,0 +1,10 @@
+from tests.client import ApiClient
+from tests.conftest import Wallet
+
+
+def test_transfer_resend_returns_original_deploy(client: ApiClient, prepopulated_wallet: Wallet, wallet: Wallet):
+    first, second = client.wallets.send_transfer_twice(from_wallet=prepopulated_wallet, to_wallet=wallet, amount=10000)
+
+    assert first.status == 200
+    assert second.status == 200
+    assert second.json["deploy_id"] == first.json["deploy_id"]
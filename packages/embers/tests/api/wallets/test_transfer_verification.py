@@ -0,0 +1,13 @@
+from tests.client import ApiClient
+from tests.conftest import Wallet
+
+
+def test_transfer_signed_by_other_wallet(client: ApiClient, prepopulated_wallet: Wallet, wallet: Wallet):
+    resp = client.wallets.send_transfer_signed_by(
+        signer=wallet,
+        from_wallet=prepopulated_wallet,
+        to_wallet=wallet,
+        amount=10000,
+    )
+    assert resp.status == 400
+    assert resp.json["reason"] == "deployer_mismatch"
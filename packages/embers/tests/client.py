@@ -173,6 +173,36 @@ class WalletsApi:
             accepted=self._client.listeners[from_wallet.address].register(resp_next.json["deploy_id"]),
         )
 
+    def send_transfer_signed_by(self, signer: Wallet, from_wallet: Wallet, to_wallet: Wallet, amount: int) -> Responce:
+        prepare_request = {"from": from_wallet.address, "to": to_wallet.address, "amount": amount}
+        resp = self._client.post("/wallets/transfer/prepare", json=prepare_request)
+        assert resp.status == 200
+
+        return self._client.post(
+            "/wallets/transfer/send",
+            json={
+                "prepare_request": prepare_request,
+                "prepare_response": resp.json["response"],
+                "request": sing_contract(signer, resp.json["response"]["contract"]),
+                "token": resp.json["token"],
+            },
+        )
+
+    def send_transfer_twice(self, from_wallet: Wallet, to_wallet: Wallet, amount: int) -> tuple[Responce, Responce]:
+        prepare_request = {"from": from_wallet.address, "to": to_wallet.address, "amount": amount}
+        resp = self._client.post("/wallets/transfer/prepare", json=prepare_request)
+        assert resp.status == 200
+
+        body = {
+            "prepare_request": prepare_request,
+            "prepare_response": resp.json["response"],
+            "request": sing_contract(from_wallet, resp.json["response"]["contract"]),
+            "token": resp.json["token"],
+        }
+        first = self._client.post("/wallets/transfer/send", json=body)
+        second = self._client.post("/wallets/transfer/send", json=body)
+        return first, second
+
     def boost(
         self,
         from_wallet: Wallet,
@@ -320,7 +350,12 @@ class AgentsApi:
 
         resp_next = self._client.post(
             f"/ai-agents/{agent_id}/delete/send",
-            json=sing_contract(wallet, resp.json["contract"]),
+            json={
+                "prepare_request": {"id": agent_id},
+                "prepare_response": resp.json["response"],
+                "request": sing_contract(wallet, resp.json["response"]["contract"]),
+                "token": resp.json["token"],
+            },
         )
         assert resp_next.status == 200
 
@@ -501,7 +536,12 @@ class AgentsTeamsApi:
 
         resp_next = self._client.post(
             f"/ai-agents-teams/{agent_id}/delete/send",
-            json=sing_contract(wallet, resp.json["contract"]),
+            json={
+                "prepare_request": {"id": agent_id},
+                "prepare_response": resp.json["response"],
+                "request": sing_contract(wallet, resp.json["response"]["contract"]),
+                "token": resp.json["token"],
+            },
         )
         assert resp_next.status == 200
 
@@ -652,7 +692,12 @@ class OslfsApi:
 
         resp_next = self._client.post(
             f"/oslfs/{oslf_id}/delete/send",
-            json=sing_contract(wallet, resp.json["contract"]),
+            json={
+                "prepare_request": {"id": oslf_id},
+                "prepare_response": resp.json["response"],
+                "request": sing_contract(wallet, resp.json["response"]["contract"]),
+                "token": resp.json["token"],
+            },
         )
         assert resp_next.status == 200
 
@@ -663,6 +708,20 @@ class OslfsApi:
         )
 
 
+class ExplorerApi:
+    def __init__(self, client: HttpClient):
+        self._client = client
+
+    def get_block(self, block_hash: str) -> Responce:
+        return self._client.get(f"/explorer/blocks/{block_hash}")
+
+    def list_blocks(self, from_height: int, to_height: int) -> Responce:
+        return self._client.get(f"/explorer/blocks?from={from_height}&to={to_height}")
+
+    def main_chain(self, depth: int) -> Responce:
+        return self._client.get(f"/explorer/main-chain?depth={depth}")
+
+
 class ApiClient:
     def __init__(self, backend_url: str):
         self._http_client = HttpClient(backend_url)
@@ -671,6 +730,7 @@ class ApiClient:
         self.agents = AgentsApi(self._http_client)
         self.agents_teams = AgentsTeamsApi(self._http_client)
         self.oslfs = OslfsApi(self._http_client)
+        self.explorer = ExplorerApi(self._http_client)
 
 
 def sing_contract(wallet: Wallet, contract: Any) -> dict:
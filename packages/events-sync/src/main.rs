@@ -11,6 +11,7 @@ use base64::prelude::BASE64_STANDARD;
 use clap::{Parser, Subcommand};
 use contracts::{rho_init_events_channels, rho_subscribe_to_service, rho_unsubscribe_from_service};
 use firefly_client::CommunicationService;
+use firefly_client::keystore::WalletKeyArgs;
 use firefly_client::models::{BlockId, DeployData};
 use futures::stream::select_all;
 use futures::{FutureExt, SinkExt, Stream, StreamExt, TryStreamExt, future};
@@ -26,9 +27,8 @@ use warp::Filter;
 
 #[derive(Debug, Parser)]
 struct Args {
-    /// Wallet key in hex format
-    #[arg(long)]
-    wallet_key: SecretKey,
+    #[command(flatten)]
+    wallet: WalletKeyArgs,
 
     /// Firefly deploy service url
     #[arg(long)]
@@ -89,6 +89,7 @@ enum Commands {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    let wallet_key = args.wallet.load().context("failed to load wallet key")?;
 
     let mut client =
         firefly_client::WriteNodeClient::new(args.deploy_service_url, args.propose_service_url)
@@ -111,7 +112,7 @@ async fn main() -> anyhow::Result<()> {
             let task = tokio::spawn(async move {
                 let mut all_sources = select_all([
                     subscribe_to_firefly(
-                        &args.wallet_key,
+                        &wallet_key,
                         client,
                         args.service_id,
                         &external_hostname,
@@ -180,7 +181,7 @@ async fn main() -> anyhow::Result<()> {
                     let rho_code = rho_save_events(channel_name, &events)?;
                     let deploy_data = DeployData::builder(rho_code).build();
                     let hash = client
-                        .full_deploy(&args.wallet_key, deploy_data)
+                        .full_deploy(&wallet_key, deploy_data)
                         .await
                         .context("failed save events")?;
                     println!("events deployed");
@@ -194,7 +195,7 @@ async fn main() -> anyhow::Result<()> {
                     );
                     let deploy_data = DeployData::builder(rho_code).build();
                     client
-                        .full_deploy(&args.wallet_key, deploy_data)
+                        .full_deploy(&wallet_key, deploy_data)
                         .await
                         .context("failed to notify listeners")?;
                     println!("notified");
@@ -212,7 +213,7 @@ async fn main() -> anyhow::Result<()> {
             let rho_code = rho_init_events_channels(&args.service_id);
             let deploy_data = DeployData::builder(rho_code).build();
             let hash = client
-                .full_deploy(&args.wallet_key, deploy_data)
+                .full_deploy(&wallet_key, deploy_data)
                 .await
                 .context("failed to init channels")?;
             println!("{hash}");
@@ -376,7 +377,10 @@ async fn subscribe_to_firefly(
             let bytes: Vec<u8> = client
                 .get_channel_value(event.block_hash, event.channel_name)
                 .await
-                .context("failed to get events")?;
+                .context("failed to get events")?
+                .pop()
+                .context("no events on channel")?
+                .value;
 
             let events: Vec<Entry> =
                 bitcode::deserialize(&bytes).context("failed to deserialize events")?;
@@ -0,0 +1,210 @@
+use darling::util::Flag;
+use darling::{FromDeriveInput, FromField, FromVariant, ast};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, parse_macro_input};
+
+#[derive(Debug, Clone, FromDeriveInput)]
+#[darling(attributes(from_expr), supports(struct_any, enum_any))]
+struct Args {
+    ident: syn::Ident,
+    generics: syn::Generics,
+    data: ast::Data<VariantArgs, FieldArgs>,
+}
+
+#[derive(Debug, Clone, FromVariant)]
+#[darling(attributes(from_expr))]
+struct VariantArgs {
+    ident: syn::Ident,
+    fields: ast::Fields<FieldArgs>,
+    rename: Option<String>,
+}
+
+#[derive(Debug, Clone, FromField)]
+#[darling(attributes(from_expr))]
+struct FieldArgs {
+    ident: Option<syn::Ident>,
+    rename: Option<String>,
+    skip: Flag,
+}
+
+pub fn from_expr_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let args = match Args::from_derive_input(&input) {
+        Ok(v) => v,
+        Err(err) => return err.write_errors().into(),
+    };
+
+    TokenStream::from(impl_from_expr(args))
+}
+
+fn impl_from_expr(
+    Args {
+        ident,
+        generics,
+        data,
+    }: Args,
+) -> proc_macro2::TokenStream {
+    let (from_impl, from_par_impl) = match data {
+        ast::Data::Struct(fields) => impl_for_struct(fields),
+        ast::Data::Enum(variants) => (impl_for_enum(&ident, variants), quote! {}),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::firefly_client::helpers::FromExpr for #ident #ty_generics
+            #where_clause
+        {
+            fn from(
+                val: ::firefly_client::models::rhoapi::expr::ExprInstance,
+            ) -> ::firefly_client::helpers::_dependencies::anyhow::Result<Self> {
+                #from_impl
+            }
+
+            #from_par_impl
+        }
+    }
+}
+
+fn impl_for_struct(
+    fields: ast::Fields<FieldArgs>,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    match fields.style {
+        // newtypes are transparent, so `Option` and unforgeable names keep working inside them
+        ast::Style::Tuple if fields.fields.len() == 1 && !fields.fields[0].skip.is_present() => (
+            quote! {
+                ::firefly_client::helpers::FromExpr::from(val).map(Self)
+            },
+            quote! {
+                fn from_par(
+                    par: ::firefly_client::models::rhoapi::Par,
+                ) -> ::firefly_client::helpers::_dependencies::anyhow::Result<Self> {
+                    ::firefly_client::helpers::FromExpr::from_par(par).map(Self)
+                }
+            },
+        ),
+        _ => (
+            decode_fields(&quote! { Self }, fields, &quote! { val }),
+            quote! {},
+        ),
+    }
+}
+
+fn impl_for_enum(ident: &syn::Ident, variants: Vec<VariantArgs>) -> proc_macro2::TokenStream {
+    let enum_name = ident.to_string();
+
+    let arms = variants.into_iter().map(|variant| {
+        let variant_ident = variant.ident;
+        let variant_name = variant
+            .rename
+            .unwrap_or_else(|| variant_ident.to_string());
+        let constructor = quote! { Self::#variant_ident };
+
+        match variant.fields.style {
+            ast::Style::Unit => quote! {
+                (#variant_name, ::std::option::Option::None) => ::std::result::Result::Ok(#constructor),
+            },
+            ast::Style::Tuple
+                if variant.fields.fields.len() == 1 && !variant.fields.fields[0].skip.is_present() =>
+            {
+                quote! {
+                (#variant_name, ::std::option::Option::Some(payload)) => {
+                    ::firefly_client::helpers::_dependencies::anyhow::Context::with_context(
+                        ::firefly_client::helpers::FromExpr::from_par(payload).map(#constructor),
+                        || ::std::format!("invalid variant `{}`", #variant_name),
+                    )
+                }
+            }
+            }
+            _ => {
+                let decode = decode_fields(&constructor, variant.fields, &quote! { payload });
+                quote! {
+                    (#variant_name, ::std::option::Option::Some(payload)) => {
+                        let payload = ::firefly_client::helpers::_dependencies::anyhow::Context::with_context(
+                            ::firefly_client::helpers::expr_instance(payload),
+                            || ::std::format!("invalid variant `{}`", #variant_name),
+                        )?;
+                        #decode
+                    }
+                }
+            }
+        }
+    });
+
+    quote! {
+        let (tag, payload) = ::firefly_client::helpers::enum_variant(val)?;
+        match (tag.as_str(), payload) {
+            #(#arms)*
+            (other, _) => ::std::result::Result::Err(
+                ::firefly_client::helpers::_dependencies::anyhow::anyhow!(
+                    "unknown variant `{other}` of {}",
+                    #enum_name,
+                ),
+            ),
+        }
+    }
+}
+
+/// Decodes named fields from `EMapBody` and unnamed ones from `ETupleBody`, skipped fields get their default.
+fn decode_fields(
+    constructor: &proc_macro2::TokenStream,
+    fields: ast::Fields<FieldArgs>,
+    source: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let default = quote! { ::std::default::Default::default() };
+
+    match fields.style {
+        ast::Style::Struct => {
+            let field_initializers = fields.fields.into_iter().map(|f| {
+                let field_name = f.ident.unwrap();
+                if f.skip.is_present() {
+                    return quote! { #field_name: #default };
+                }
+                let field_name_str = f.rename.unwrap_or_else(|| field_name.to_string());
+                quote! {
+                    #field_name: ::firefly_client::helpers::take_field(&mut entries, #field_name_str)?
+                }
+            });
+            quote! {
+                let mut entries = ::firefly_client::helpers::map_entries(#source)?;
+                ::std::result::Result::Ok(#constructor { #(#field_initializers),* })
+            }
+        }
+        ast::Style::Tuple if fields.fields.iter().any(|f| !f.skip.is_present()) => {
+            let len = fields
+                .fields
+                .iter()
+                .filter(|f| !f.skip.is_present())
+                .count();
+            let mut index = 0usize;
+            let field_initializers = fields.fields.iter().map(|f| {
+                if f.skip.is_present() {
+                    return default.clone();
+                }
+                let i = index;
+                index += 1;
+                quote! {
+                    ::firefly_client::helpers::take_element(&mut elements, #i)?
+                }
+            });
+            let field_initializers: Vec<_> = field_initializers.collect();
+            quote! {
+                let mut elements = ::firefly_client::helpers::tuple_elements(#source, #len)?.into_iter();
+                ::std::result::Result::Ok(#constructor(#(#field_initializers),*))
+            }
+        }
+        ast::Style::Tuple => {
+            let field_initializers = fields.fields.iter().map(|_| default.clone());
+            quote! {
+                ::firefly_client::helpers::tuple_elements(#source, 0)?;
+                ::std::result::Result::Ok(#constructor(#(#field_initializers),*))
+            }
+        }
+        ast::Style::Unit => quote! {
+            ::firefly_client::helpers::tuple_elements(#source, 0)?;
+            ::std::result::Result::Ok(#constructor)
+        },
+    }
+}
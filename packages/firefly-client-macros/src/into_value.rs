@@ -1,22 +1,49 @@
-use darling::{FromDeriveInput, FromField, ast};
+use darling::util::Flag;
+use darling::{FromDeriveInput, FromField, FromVariant, ast};
 use proc_macro::TokenStream;
 use proc_macro2::Literal;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{DeriveInput, parse_macro_input};
 
 #[derive(Debug, Clone, FromDeriveInput)]
-#[darling(supports(struct_any))]
+#[darling(attributes(into_value), supports(struct_any, enum_any))]
 struct Args {
     ident: syn::Ident,
     generics: syn::Generics,
-    data: ast::Data<(), FieldArgs>,
+    data: ast::Data<VariantArgs, FieldArgs>,
+    tag: Option<String>,
+    content: Option<String>,
+    untagged: Flag,
+}
+
+#[derive(Debug, Clone, FromVariant)]
+#[darling(attributes(into_value))]
+struct VariantArgs {
+    ident: syn::Ident,
+    fields: ast::Fields<FieldArgs>,
+    rename: Option<String>,
 }
 
 #[derive(Debug, Clone, FromField)]
-#[darling(forward_attrs(allow, cfg))]
+#[darling(attributes(into_value), forward_attrs(allow, cfg))]
 struct FieldArgs {
     ident: Option<syn::Ident>,
     attrs: Vec<syn::Attribute>,
+    rename: Option<String>,
+    skip: Flag,
+    flatten: Flag,
+}
+
+/// How enum variants are told apart in the rendered value.
+enum Tagging {
+    /// `"Variant"` for unit variants and `("Variant", payload)` tuple for the rest.
+    External,
+    /// `{"tag": "Variant", ...fields}`.
+    Internal { tag: String },
+    /// `{"tag": "Variant", "content": payload}`.
+    Adjacent { tag: String, content: String },
+    /// Just the payload.
+    Untagged,
 }
 
 pub fn into_value_derive(input: TokenStream) -> TokenStream {
@@ -27,69 +54,346 @@ pub fn into_value_derive(input: TokenStream) -> TokenStream {
         Err(err) => return err.write_errors().into(),
     };
 
-    TokenStream::from(impl_for_struct(args))
+    match impl_into_value(args) {
+        Ok(v) => TokenStream::from(v),
+        Err(err) => err.write_errors().into(),
+    }
 }
 
-fn impl_for_struct(
+fn impl_into_value(
     Args {
         ident,
         generics,
         data,
+        tag,
+        content,
+        untagged,
     }: Args,
-) -> proc_macro2::TokenStream {
-    let fields = data.take_struct().expect("expected a struct");
-    let into_rho_value_impl = match fields.style {
-        ast::Style::Tuple if fields.fields.len() == 1 => {
-            quote! {
-                ::firefly_client::rendering::IntoValue::into_value(self.0)
-            }
+) -> darling::Result<proc_macro2::TokenStream> {
+    let tagging = match (tag, content, untagged.is_present()) {
+        (None, None, false) => Tagging::External,
+        (Some(tag), None, false) => Tagging::Internal { tag },
+        (Some(tag), Some(content), false) => Tagging::Adjacent { tag, content },
+        (None, None, true) => Tagging::Untagged,
+        (None, Some(_), false) => {
+            return Err(darling::Error::custom("`content` requires `tag`").with_span(&ident));
         }
-        ast::Style::Tuple => {
-            let field_initializers = fields.fields.into_iter().enumerate().map(|(i, f)| {
-                let attrs = &f.attrs;
-                let lit = Literal::u64_unsuffixed(i as _);
-                quote! {
-                    #(#attrs)*
-                    ::firefly_client::rendering::IntoValue::into_value(self.#lit)
-                }
-            });
-            quote! {
-                ::firefly_client::rendering::Value::Tuple(::std::vec![#(#field_initializers),*])
-            }
+        (_, _, true) => {
+            return Err(
+                darling::Error::custom("`untagged` can't be combined with `tag`").with_span(&ident),
+            );
         }
-        ast::Style::Struct => {
-            let field_initializers = fields.fields.into_iter().map(|f| {
-                let field_name = f.ident.unwrap();
-                let field_name_str = field_name.to_string();
-                let attrs = &f.attrs;
-                quote! {
-                    #(#attrs)*
-                    map.insert(
-                        ::std::borrow::ToOwned::to_owned(#field_name_str),
-                        ::firefly_client::rendering::IntoValue::into_value(self.#field_name),
-                    );
-                }
-            });
-            quote! {
-                let mut map = ::std::collections::BTreeMap::new();
-                #(#field_initializers)*
-                ::firefly_client::rendering::Value::Map(map)
+    };
+
+    let (body, is_map) = match data {
+        ast::Data::Struct(fields) => {
+            if !matches!(tagging, Tagging::External) {
+                return Err(darling::Error::custom(
+                    "tagging attributes are only supported on enums",
+                )
+                .with_span(&ident));
             }
+            impl_for_struct(fields)?
         }
-        ast::Style::Unit => quote! {
-            ::firefly_client::rendering::Value::Tuple(::std::vec![])
-        },
+        ast::Data::Enum(variants) => impl_for_enum(variants, &tagging)?,
     };
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    quote! {
+    if !is_map {
+        return Ok(quote! {
+            impl #impl_generics ::firefly_client::rendering::IntoValue for #ident #ty_generics
+                #where_clause
+            {
+                fn into_value(self) -> ::firefly_client::rendering::Value {
+                    #body
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::firefly_client::rendering::IntoMap for #ident #ty_generics
+            #where_clause
+        {
+            fn into_map(
+                self,
+            ) -> ::std::collections::BTreeMap<
+                ::std::string::String,
+                ::firefly_client::rendering::Value,
+            > {
+                #body
+            }
+        }
+
         impl #impl_generics ::firefly_client::rendering::IntoValue for #ident #ty_generics
             #where_clause
         {
             fn into_value(self) -> ::firefly_client::rendering::Value {
-                #into_rho_value_impl
+                ::firefly_client::rendering::Value::Map(
+                    ::firefly_client::rendering::IntoMap::into_map(self),
+                )
+            }
+        }
+    })
+}
+
+/// Returns the body of `into_value`, or of `into_map` if the second element is `true`.
+fn impl_for_struct(
+    fields: ast::Fields<FieldArgs>,
+) -> darling::Result<(proc_macro2::TokenStream, bool)> {
+    let accessors = fields
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            f.ident.as_ref().map_or_else(
+                || {
+                    let lit = Literal::u64_unsuffixed(i as _);
+                    quote! { self.#lit }
+                },
+                |ident| quote! { self.#ident },
+            )
+        })
+        .collect();
+
+    if fields.style == ast::Style::Struct {
+        return Ok((encode_map(fields, accessors), true));
+    }
+    encode_fields(fields, accessors).map(|body| (body, false))
+}
+
+/// Returns the body of `into_value`, or of `into_map` if the second element is `true`.
+fn impl_for_enum(
+    variants: Vec<VariantArgs>,
+    tagging: &Tagging,
+) -> darling::Result<(proc_macro2::TokenStream, bool)> {
+    let arms = variants
+        .into_iter()
+        .map(|variant| {
+            let variant_ident = &variant.ident;
+            let variant_name = variant
+                .rename
+                .clone()
+                .unwrap_or_else(|| variant_ident.to_string());
+            let tag_value = quote! {
+                ::firefly_client::rendering::Value::String(
+                    ::std::borrow::ToOwned::to_owned(#variant_name),
+                )
+            };
+
+            let (pattern, accessors) = bind_variant_fields(&variant);
+
+            if variant.fields.style == ast::Style::Unit {
+                let value = match tagging {
+                    Tagging::External => tag_value,
+                    Tagging::Internal { tag } | Tagging::Adjacent { tag, .. } => quote! {
+                        ::std::collections::BTreeMap::from([
+                            (::std::borrow::ToOwned::to_owned(#tag), #tag_value),
+                        ])
+                    },
+                    Tagging::Untagged => quote! { ::firefly_client::rendering::Value::Nil },
+                };
+                return Ok(quote! { #pattern => #value, });
             }
+
+            let value = match tagging {
+                Tagging::External => {
+                    let payload = encode_fields(variant.fields, accessors)?;
+                    quote! {
+                        ::firefly_client::rendering::Value::Tuple(::std::vec![#tag_value, #payload])
+                    }
+                }
+                Tagging::Internal { tag } => {
+                    let entries = match variant.fields.style {
+                        ast::Style::Tuple if variant.fields.len() == 1 => {
+                            reject_unnamed_attributes(&variant.fields)?;
+                            if variant.fields.fields[0].skip.is_present() {
+                                quote! { ::std::collections::BTreeMap::new() }
+                            } else {
+                                let accessor = &accessors[0];
+                                quote! { ::firefly_client::rendering::IntoMap::into_map(#accessor) }
+                            }
+                        }
+                        ast::Style::Tuple => {
+                            return Err(darling::Error::custom(
+                                "internally tagged tuple variants must have exactly one field",
+                            )
+                            .with_span(variant_ident));
+                        }
+                        _ => encode_map(variant.fields, accessors),
+                    };
+                    quote! {{
+                        let mut __map = #entries;
+                        __map.insert(::std::borrow::ToOwned::to_owned(#tag), #tag_value);
+                        __map
+                    }}
+                }
+                Tagging::Adjacent { tag, content } => {
+                    let payload = encode_fields(variant.fields, accessors)?;
+                    quote! {
+                        ::std::collections::BTreeMap::from([
+                            (::std::borrow::ToOwned::to_owned(#tag), #tag_value),
+                            (::std::borrow::ToOwned::to_owned(#content), #payload),
+                        ])
+                    }
+                }
+                Tagging::Untagged => encode_fields(variant.fields, accessors)?,
+            };
+
+            Ok(quote! { #pattern => #value, })
+        })
+        .collect::<darling::Result<Vec<_>>>()?;
+
+    let is_map = matches!(tagging, Tagging::Internal { .. } | Tagging::Adjacent { .. });
+    Ok((
+        quote! {
+            match self {
+                #(#arms)*
+            }
+        },
+        is_map,
+    ))
+}
+
+/// Builds match pattern for the variant along with bindings of its fields.
+fn bind_variant_fields(
+    variant: &VariantArgs,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    let variant_ident = &variant.ident;
+
+    match variant.fields.style {
+        ast::Style::Struct => {
+            let bindings: Vec<_> = variant
+                .fields
+                .iter()
+                .map(|f| {
+                    let ident = f.ident.as_ref().unwrap();
+                    quote! { #ident }
+                })
+                .collect();
+            let bound = variant
+                .fields
+                .iter()
+                .zip(&bindings)
+                .filter(|(f, _)| !f.skip.is_present())
+                .map(|(f, binding)| {
+                    let attrs = &f.attrs;
+                    quote! { #(#attrs)* #binding }
+                });
+            (quote! { Self::#variant_ident { #(#bound,)* .. } }, bindings)
         }
+        ast::Style::Tuple => {
+            let bindings: Vec<_> = (0..variant.fields.len())
+                .map(|i| {
+                    let ident = format_ident!("__field{}", i);
+                    quote! { #ident }
+                })
+                .collect();
+            let bound = variant.fields.iter().zip(&bindings).map(|(f, binding)| {
+                if f.skip.is_present() {
+                    quote! { _ }
+                } else {
+                    binding.clone()
+                }
+            });
+            (quote! { Self::#variant_ident(#(#bound),*) }, bindings)
+        }
+        ast::Style::Unit => (quote! { Self::#variant_ident }, vec![]),
     }
 }
+
+fn reject_unnamed_attributes(fields: &ast::Fields<FieldArgs>) -> darling::Result<()> {
+    if fields.style != ast::Style::Struct
+        && let Some(f) = fields
+            .iter()
+            .find(|f| f.flatten.is_present() || f.rename.is_some())
+    {
+        return Err(darling::Error::custom(
+            "`rename` and `flatten` are only supported on named fields",
+        )
+        .with_span(&f.flatten.span()));
+    }
+    Ok(())
+}
+
+/// Renders named fields into a map and unnamed ones into a tuple, single unnamed field is transparent.
+fn encode_fields(
+    fields: ast::Fields<FieldArgs>,
+    accessors: Vec<proc_macro2::TokenStream>,
+) -> darling::Result<proc_macro2::TokenStream> {
+    reject_unnamed_attributes(&fields)?;
+
+    let style = fields.style;
+    if style == ast::Style::Struct {
+        let map = encode_map(fields, accessors);
+        return Ok(quote! {
+            ::firefly_client::rendering::Value::Map(#map)
+        });
+    }
+
+    let is_newtype = style == ast::Style::Tuple && fields.len() == 1;
+    let fields: Vec<_> = fields
+        .fields
+        .into_iter()
+        .zip(accessors)
+        .filter(|(f, _)| !f.skip.is_present())
+        .collect();
+
+    if is_newtype && fields.len() == 1 {
+        let (_, accessor) = &fields[0];
+        return Ok(quote! {
+            ::firefly_client::rendering::IntoValue::into_value(#accessor)
+        });
+    }
+
+    let field_initializers = fields.iter().map(|(f, accessor)| {
+        let attrs = &f.attrs;
+        quote! {
+            #(#attrs)*
+            ::firefly_client::rendering::IntoValue::into_value(#accessor)
+        }
+    });
+    Ok(quote! {
+        ::firefly_client::rendering::Value::Tuple(::std::vec![#(#field_initializers),*])
+    })
+}
+
+/// Renders named fields into a `BTreeMap`, flattened fields have to implement `IntoMap`.
+fn encode_map(
+    fields: ast::Fields<FieldArgs>,
+    accessors: Vec<proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let field_initializers = fields
+        .fields
+        .into_iter()
+        .zip(accessors)
+        .filter(|(f, _)| !f.skip.is_present())
+        .map(|(f, accessor)| {
+            let attrs = &f.attrs;
+
+            if f.flatten.is_present() {
+                quote! {
+                    #(#attrs)*
+                    __map.extend(::firefly_client::rendering::IntoMap::into_map(#accessor));
+                }
+            } else {
+                let field_name_str = f
+                    .rename
+                    .clone()
+                    .unwrap_or_else(|| f.ident.as_ref().unwrap().to_string());
+                quote! {
+                    #(#attrs)*
+                    __map.insert(
+                        ::std::borrow::ToOwned::to_owned(#field_name_str),
+                        ::firefly_client::rendering::IntoValue::into_value(#accessor),
+                    );
+                }
+            }
+        });
+    quote! {{
+        let mut __map = ::std::collections::BTreeMap::new();
+        #(#field_initializers)*
+        __map
+    }}
+}
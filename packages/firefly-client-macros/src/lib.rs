@@ -1,9 +1,20 @@
 use proc_macro::TokenStream;
 
+mod from_expr;
 mod into_value;
 mod render;
 
-#[proc_macro_derive(IntoValue)]
+#[proc_macro_derive(FromExpr, attributes(from_expr))]
+pub fn from_expr_derive(input: TokenStream) -> TokenStream {
+    from_expr::from_expr_derive(input)
+}
+
+/// Derives `IntoValue`, plus `IntoMap` for structs with named fields and tagged enums.
+///
+/// Every field has to implement `IntoValue`. `u64` doesn't, since values above `i64::MAX`
+/// have no Rholang int, so a `u64` field fails to compile. Keep such fields as `i64` or `u32`,
+/// or as a `Value` made with the checked `Value::try_from`.
+#[proc_macro_derive(IntoValue, attributes(into_value))]
 pub fn into_value_derive(input: TokenStream) -> TokenStream {
     into_value::into_value_derive(input)
 }
@@ -4,6 +4,7 @@ fn main() -> Result<()> {
     tonic_prost_build::configure()
         .build_client(true)
         .build_server(true)
+        .generate_default_stubs(true)
         .compile_protos(
             &[
                 "protobuf/DeployServiceV1.proto",
@@ -0,0 +1,265 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt, TryStreamExt, stream};
+use secp256k1::PublicKey;
+
+use crate::WriteNodeClient;
+use crate::errors::WriteNodeError;
+use crate::models::casper::{
+    BlockInfo,
+    BondInfo,
+    DeployInfo,
+    JustificationInfo,
+    LightBlockInfo,
+    SingleReport,
+};
+use crate::models::{BlockId, DeployId};
+use crate::write_node_client::MAX_QUERY_DEPTH;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bond {
+    pub validator: PublicKey,
+    pub stake: i64,
+}
+
+/// Latest block of the validator seen by block creator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Justification {
+    pub validator: PublicKey,
+    pub latest_block: BlockId,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockSummary {
+    pub hash: BlockId,
+    pub number: u64,
+    pub timestamp: DateTime<Utc>,
+    /// `None` for genesis.
+    pub sender: Option<PublicKey>,
+    pub seq_num: u64,
+    pub shard_id: String,
+    pub parents: Vec<BlockId>,
+    pub justifications: Vec<Justification>,
+    pub bonds: Vec<Bond>,
+    pub pre_state_hash: String,
+    pub post_state_hash: String,
+    pub deploy_count: u32,
+    pub fault_tolerance: f32,
+    pub rejected_deploys: Vec<DeployId>,
+    pub finalized: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockDeploy {
+    pub id: DeployId,
+    pub deployer: PublicKey,
+    pub term: String,
+    pub timestamp: DateTime<Utc>,
+    pub phlo_price: u64,
+    pub phlo_limit: u64,
+    pub valid_after_block_number: u64,
+    pub cost: u64,
+    pub errored: bool,
+    pub system_deploy_error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub summary: BlockSummary,
+    pub deploys: Vec<BlockDeploy>,
+}
+
+/// Deploy with the tuplespace events it produced, as replayed by the node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeployReport {
+    pub deploy: BlockDeploy,
+    pub reports: Vec<SingleReport>,
+}
+
+/// Typed read access to blocks, on top of [`WriteNodeClient`] block rpcs.
+#[derive(Clone)]
+pub struct BlockExplorer {
+    client: WriteNodeClient,
+}
+
+impl BlockExplorer {
+    pub const fn new(client: WriteNodeClient) -> Self {
+        Self { client }
+    }
+
+    pub async fn block(&self, hash: &BlockId) -> Result<Block, WriteNodeError> {
+        let (mut client, mut finality_client) = (self.client.clone(), self.client.clone());
+        let (
+            BlockInfo {
+                block_info,
+                deploys,
+            },
+            finalized,
+        ) = tokio::try_join!(client.get_block(hash), finality_client.is_finalized(hash))?;
+
+        Ok(Block {
+            summary: summary(block_info.unwrap_or_default(), finalized)?,
+            deploys: deploys.into_iter().map(deploy).collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Blocks of the `depth` latest heights, including ones off the main chain.
+    pub async fn recent_blocks(&self, depth: u32) -> Result<Vec<BlockSummary>, WriteNodeError> {
+        let blocks = self.client.clone().get_blocks(depth).await?;
+        self.summaries(blocks).await
+    }
+
+    /// `depth` latest blocks of the main chain, newest first.
+    pub async fn main_chain(&self, depth: u32) -> Result<Vec<BlockSummary>, WriteNodeError> {
+        let blocks = self.client.clone().show_main_chain(depth).await?;
+        self.summaries(blocks).await
+    }
+
+    /// Streams blocks with numbers in `heights`, fetching them page by page.
+    pub fn blocks_by_heights(
+        &self,
+        heights: RangeInclusive<u64>,
+    ) -> impl Stream<Item = Result<Block, WriteNodeError>> + use<> {
+        let (start, end) = heights.into_inner();
+        let depth: u64 = MAX_QUERY_DEPTH.into();
+        let pages = (start..=end)
+            .step_by(MAX_QUERY_DEPTH as usize)
+            .map(move |page_start| (page_start, end.min(page_start.saturating_add(depth - 1))));
+
+        let client = self.client.clone();
+        let explorer = self.clone();
+
+        stream::iter(pages)
+            .then(move |(page_start, page_end)| {
+                let mut client = client.clone();
+                async move { client.get_blocks_by_heights(page_start, page_end).await }
+            })
+            .map_ok(|blocks| stream::iter(blocks).map(Ok))
+            .try_flatten()
+            .and_then(move |block| {
+                let explorer = explorer.clone();
+                async move { explorer.block(&block.block_hash.into()).await }
+            })
+    }
+
+    /// DAG of `depth` heights starting from `start_block_number` in graphviz dot format.
+    pub async fn dag(&self, depth: u32, start_block_number: u32) -> Result<String, WriteNodeError> {
+        self.client
+            .clone()
+            .visualize_dag(depth, start_block_number)
+            .await
+    }
+
+    /// Replays the block and returns events of each of its deploys.
+    pub async fn block_reports(&self, hash: &BlockId) -> Result<Vec<DeployReport>, WriteNodeError> {
+        self.client
+            .clone()
+            .get_event_by_hash(hash)
+            .await?
+            .deploys
+            .into_iter()
+            .map(|info| {
+                Ok(DeployReport {
+                    deploy: deploy(info.deploy_info.unwrap_or_default())?,
+                    reports: info.report,
+                })
+            })
+            .collect()
+    }
+
+    async fn summaries(
+        &self,
+        blocks: Vec<LightBlockInfo>,
+    ) -> Result<Vec<BlockSummary>, WriteNodeError> {
+        futures::future::try_join_all(blocks.into_iter().map(|block| async move {
+            let finalized = self
+                .client
+                .clone()
+                .is_finalized(&block.block_hash.clone().into())
+                .await?;
+            summary(block, finalized)
+        }))
+        .await
+    }
+}
+
+fn summary(block: LightBlockInfo, finalized: bool) -> Result<BlockSummary, WriteNodeError> {
+    Ok(BlockSummary {
+        sender: (!block.sender.is_empty())
+            .then(|| public_key(&block.sender))
+            .transpose()?,
+        timestamp: timestamp(block.timestamp)?,
+        hash: block.block_hash.into(),
+        number: block.block_number as _,
+        seq_num: block.seq_num as _,
+        shard_id: block.shard_id,
+        parents: block
+            .parents_hash_list
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+        justifications: block
+            .justifications
+            .into_iter()
+            .map(
+                |JustificationInfo {
+                     validator,
+                     latest_block_hash,
+                 }| {
+                    Ok(Justification {
+                        validator: public_key(&validator)?,
+                        latest_block: latest_block_hash.into(),
+                    })
+                },
+            )
+            .collect::<Result<_, WriteNodeError>>()?,
+        bonds: block
+            .bonds
+            .into_iter()
+            .map(|BondInfo { validator, stake }| {
+                Ok(Bond {
+                    validator: public_key(&validator)?,
+                    stake,
+                })
+            })
+            .collect::<Result<_, WriteNodeError>>()?,
+        pre_state_hash: block.pre_state_hash,
+        post_state_hash: block.post_state_hash,
+        deploy_count: block.deploy_count as _,
+        fault_tolerance: block.fault_tolerance,
+        rejected_deploys: block
+            .rejected_deploys
+            .into_iter()
+            .map(|rejected| rejected.sig.into())
+            .collect(),
+        finalized,
+    })
+}
+
+fn deploy(deploy: DeployInfo) -> Result<BlockDeploy, WriteNodeError> {
+    Ok(BlockDeploy {
+        deployer: public_key(&deploy.deployer)?,
+        timestamp: timestamp(deploy.timestamp)?,
+        id: deploy.sig.into(),
+        term: deploy.term,
+        phlo_price: deploy.phlo_price as _,
+        phlo_limit: deploy.phlo_limit as _,
+        valid_after_block_number: deploy.valid_after_block_number as _,
+        cost: deploy.cost,
+        errored: deploy.errored,
+        system_deploy_error: (!deploy.system_deploy_error.is_empty())
+            .then_some(deploy.system_deploy_error),
+    })
+}
+
+fn public_key(hex: &str) -> Result<PublicKey, WriteNodeError> {
+    PublicKey::from_str(hex)
+        .map_err(|err| WriteNodeError::ResponseFormat(format!("invalid public key {hex}: {err}")))
+}
+
+fn timestamp(millis: i64) -> Result<DateTime<Utc>, WriteNodeError> {
+    DateTime::from_timestamp_millis(millis)
+        .ok_or_else(|| WriteNodeError::ResponseFormat(format!("invalid timestamp {millis}")))
+}
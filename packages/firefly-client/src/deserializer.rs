@@ -0,0 +1,290 @@
+use serde::de::value::{MapDeserializer, SeqDeserializer, StringDeserializer};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::errors::DeserializeParError;
+use crate::models::rhoapi::expr::ExprInstance;
+use crate::models::rhoapi::g_unforgeable::UnfInstance;
+use crate::models::rhoapi::{Expr, Par};
+
+/// Deserializes `T` straight from process returned by node.
+pub fn from_par<T>(par: Par) -> Result<T, DeserializeParError>
+where
+    T: de::DeserializeOwned,
+{
+    T::deserialize(ParDeserializer::new(par))
+}
+
+/// [`de::Deserializer`] over [`Par`] holding a single data value.
+///
+/// Tuples, lists and sets are sequences, maps are maps, `Nil` is unit,
+/// uris are strings and unforgeable names are hex strings of their ids.
+/// Byte arrays are bytes, but are hex encoded when string is requested.
+/// Enums are either `"Variant"` strings, `("Variant", payload)` tuples
+/// or single entry `{"Variant": payload}` maps.
+#[derive(Debug, Clone)]
+pub struct ParDeserializer {
+    par: Par,
+}
+
+impl ParDeserializer {
+    pub const fn new(par: Par) -> Self {
+        Self { par }
+    }
+
+    fn is_nil(&self) -> bool {
+        self.par == Par::default()
+    }
+
+    fn into_value(self) -> Result<ParValue, DeserializeParError> {
+        if self.is_nil() {
+            return Ok(ParValue::Nil);
+        }
+
+        let Par {
+            exprs,
+            unforgeables,
+            ..
+        } = self.par;
+
+        if let Some(expr) = exprs.into_iter().next() {
+            return expr
+                .expr_instance
+                .map(ParValue::Expr)
+                .ok_or_else(|| de::Error::custom("missing expr_instance"));
+        }
+
+        match unforgeables.into_iter().next().and_then(|u| u.unf_instance) {
+            Some(UnfInstance::GPrivateBody(body)) => Ok(ParValue::Unforgeable(body.id)),
+            Some(UnfInstance::GDeployIdBody(body)) => Ok(ParValue::Unforgeable(body.sig)),
+            Some(UnfInstance::GDeployerIdBody(body)) => Ok(ParValue::Unforgeable(body.public_key)),
+            Some(other) => Err(de::Error::custom(format!(
+                "unsupported unforgeable: {other:?}"
+            ))),
+            None => Err(de::Error::custom("process is not a data value")),
+        }
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+enum ParValue {
+    Nil,
+    Expr(ExprInstance),
+    Unforgeable(Vec<u8>),
+}
+
+impl IntoDeserializer<'_, DeserializeParError> for ParDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl IntoDeserializer<'_, DeserializeParError> for Par {
+    type Deserializer = ParDeserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ParDeserializer::new(self)
+    }
+}
+
+impl IntoDeserializer<'_, DeserializeParError> for Expr {
+    type Deserializer = ParDeserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ParDeserializer::new(Par {
+            exprs: vec![self],
+            ..Default::default()
+        })
+    }
+}
+
+fn visit_seq<'de, V>(ps: Vec<Par>, visitor: V) -> Result<V::Value, DeserializeParError>
+where
+    V: Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(ps.into_iter().map(ParDeserializer::new));
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+impl<'de> de::Deserializer<'de> for ParDeserializer {
+    type Error = DeserializeParError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.into_value()? {
+            ParValue::Nil => visitor.visit_unit(),
+            ParValue::Unforgeable(id) => visitor.visit_string(hex::encode(id)),
+            ParValue::Expr(expr) => match expr {
+                ExprInstance::GBool(value) => visitor.visit_bool(value),
+                ExprInstance::GInt(value) => visitor.visit_i64(value),
+                ExprInstance::GString(value) | ExprInstance::GUri(value) => {
+                    visitor.visit_string(value)
+                }
+                ExprInstance::GByteArray(value) => visitor.visit_byte_buf(value),
+                ExprInstance::ETupleBody(tuple) => visit_seq(tuple.ps, visitor),
+                ExprInstance::EListBody(list) => visit_seq(list.ps, visitor),
+                ExprInstance::ESetBody(set) => visit_seq(set.ps, visitor),
+                ExprInstance::EMapBody(map) => {
+                    let mut map = MapDeserializer::new(map.kvs.into_iter().map(|pair| {
+                        (
+                            Self::new(pair.key.unwrap_or_default()),
+                            Self::new(pair.value.unwrap_or_default()),
+                        )
+                    }));
+                    let value = visitor.visit_map(&mut map)?;
+                    map.end()?;
+                    Ok(value)
+                }
+                other => Err(de::Error::custom(format!("unsupported expr: {other:?}"))),
+            },
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.into_value()? {
+            ParValue::Expr(ExprInstance::GByteArray(value)) => {
+                visitor.visit_string(hex::encode(value))
+            }
+            ParValue::Expr(expr) => de::Deserializer::deserialize_any(
+                Expr {
+                    expr_instance: Some(expr),
+                }
+                .into_deserializer(),
+                visitor,
+            ),
+            ParValue::Unforgeable(id) => visitor.visit_string(hex::encode(id)),
+            ParValue::Nil => visitor.visit_unit(),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let (tag, payload) = match self.into_value()? {
+            ParValue::Expr(ExprInstance::GString(tag)) => (tag, None),
+            ParValue::Expr(ExprInstance::ETupleBody(tuple)) if tuple.ps.len() == 2 => {
+                let mut ps = tuple.ps.into_iter();
+                let tag = de::Deserialize::deserialize(Self::new(ps.next().unwrap_or_default()))?;
+                (tag, ps.next())
+            }
+            ParValue::Expr(ExprInstance::EMapBody(map)) if map.kvs.len() == 1 => {
+                let pair = map.kvs.into_iter().next().unwrap_or_default();
+                let tag = de::Deserialize::deserialize(Self::new(pair.key.unwrap_or_default()))?;
+                (tag, pair.value)
+            }
+            _ => return Err(de::Error::custom("expected enum variant")),
+        };
+
+        visitor.visit_enum(VariantDeserializer { tag, payload })
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct VariantDeserializer {
+    tag: String,
+    payload: Option<Par>,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantDeserializer {
+    type Error = DeserializeParError;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let tag: StringDeserializer<DeserializeParError> = self.tag.clone().into_deserializer();
+        let value = seed.deserialize(tag)?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = DeserializeParError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        self.payload.map_or(Ok(()), |par| {
+            de::Deserialize::deserialize(ParDeserializer::new(par))
+        })
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(ParDeserializer::new(self.payload.unwrap_or_default()))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_tuple(
+            ParDeserializer::new(self.payload.unwrap_or_default()),
+            len,
+            visitor,
+        )
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(
+            ParDeserializer::new(self.payload.unwrap_or_default()),
+            visitor,
+        )
+    }
+}
@@ -1,11 +1,209 @@
+use crate::models::{WalletAddress, servicemodelapi};
+
 #[derive(Debug, thiserror::Error)]
 pub enum ReadNodeError {
     #[error("contract did not return any value")]
     ReturnValueMissing,
     #[error("read node returned error: status {0}, body {1}")]
     Api(reqwest::StatusCode, String),
+    #[error("read node returned service error: {0}")]
+    Service(ServiceError),
+    #[error("operation is only supported over grpc transport")]
+    GrpcTransportRequired,
     #[error("failed to deserialize: {0}")]
     Deserialization(anyhow::Error),
     #[error("http transport error: {0}")]
     Transport(#[from] reqwest::Error),
+    #[error("grpc transport error: {0}")]
+    Grpc(#[from] tonic::Status),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WriteNodeError {
+    #[error("failed to connect to node: {0}")]
+    Connection(#[from] tonic::transport::Error),
+    #[error("grpc transport error: {0}")]
+    Transport(#[from] tonic::Status),
+    #[error("node returned service error: {0}")]
+    Service(ServiceError),
+    #[error("propose refused: {0}")]
+    ProposeRefused(ProposeRefusal),
+    #[error("unexpected response format: {0}")]
+    ResponseFormat(String),
+    #[error("invalid signed contract: {0}")]
+    InvalidContract(#[from] prost::DecodeError),
+    #[error("invalid deploy id: {0}")]
+    InvalidDeployId(String),
+    #[error("failed to decode value: {0}")]
+    Decoding(anyhow::Error),
+    #[error("no validators to send request to")]
+    NoValidators,
+}
+
+impl WriteNodeError {
+    /// Whether request failed before node answered, so sending it again may succeed.
+    /// Other statuses come from the node itself and would come again.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Connection(_) => true,
+            Self::Transport(status) => matches!(
+                status.code(),
+                tonic::Code::Unavailable | tonic::Code::DeadlineExceeded
+            ),
+            _ => false,
+        }
+    }
+}
+
+/// Node `ServiceError` classified by its messages.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ServiceError {
+    #[error("insufficient phlo: {0}")]
+    InsufficientPhlo(String),
+    #[error("invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("deploy expired: {0}")]
+    Expired(String),
+    #[error("duplicate deploy: {0}")]
+    DuplicateDeploy(String),
+    #[error("deploy not found: {0}")]
+    DeployNotFound(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Message `findDeploy` answers with while deploy is in no block.
+const DEPLOY_NOT_FOUND: &str = "Couldn't find block containing deploy with id:";
+
+/// Lowercased fragments of messages nodes reject deploys with, by kind.
+const INVALID_SIGNATURE: &[&str] = &[
+    "invalid signature",
+    "invalid signing algorithm",
+    "signature verification failed",
+];
+const INSUFFICIENT_PHLO: &[&str] = &[
+    "insufficient phlo",
+    "not enough phlo",
+    "out of phlo",
+    "is less than minimum price",
+];
+const EXPIRED: &[&str] = &["expired", "validafterblocknumber", "valid after block"];
+const DUPLICATE_DEPLOY: &[&str] = &["duplicate deploy", "deploy already exists"];
+
+impl From<servicemodelapi::ServiceError> for ServiceError {
+    fn from(value: servicemodelapi::ServiceError) -> Self {
+        let message = value.messages.join("; ");
+        let lowercase = message.to_lowercase();
+        let matches =
+            |patterns: &[&str]| patterns.iter().any(|pattern| lowercase.contains(pattern));
+
+        if message.starts_with(DEPLOY_NOT_FOUND) {
+            Self::DeployNotFound(message)
+        } else if matches(INVALID_SIGNATURE) {
+            Self::InvalidSignature(message)
+        } else if matches(INSUFFICIENT_PHLO) {
+            Self::InsufficientPhlo(message)
+        } else if matches(EXPIRED) {
+            Self::Expired(message)
+        } else if matches(DUPLICATE_DEPLOY) {
+            Self::DuplicateDeploy(message)
+        } else {
+            Self::Other(message)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProposeRefusal {
+    #[error("no new deploys")]
+    NoNewDeploys,
+    #[error("validator is not bonded")]
+    NotBonded,
+    #[error("must wait for more blocks from other validators")]
+    NotEnoughNewBlocks,
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<servicemodelapi::ServiceError> for ProposeRefusal {
+    fn from(value: servicemodelapi::ServiceError) -> Self {
+        let message = value.messages.join("; ");
+        let normalized = message.to_lowercase().replace([' ', '_', '-'], "");
+
+        if normalized.contains("nonewdeploys") {
+            Self::NoNewDeploys
+        } else if normalized.contains("notbonded") {
+            Self::NotBonded
+        } else if normalized.contains("notenoughnewblock")
+            || normalized.contains("mustwaitformoreblocks")
+        {
+            Self::NotEnoughNewBlocks
+        } else {
+            Self::Other(message)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProposerError {
+    #[error("proposer is stopped")]
+    Stopped,
+    #[error("deploy is not included in a block after {attempts} proposes: {reason}")]
+    NotIncluded { attempts: usize, reason: String },
+}
+
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{0}")]
+pub struct DeserializeParError(String);
+
+impl serde::de::Error for DeserializeParError {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        Self(msg.to_string())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    #[error("failed to read {}: {source}", path.display())]
+    Io {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("invalid keystore format: {0}")]
+    Format(#[from] serde_json::Error),
+    #[error("unsupported keystore {0}")]
+    Unsupported(String),
+    #[error("invalid keystore params: {0}")]
+    InvalidParams(String),
+    #[error("wrong keystore password")]
+    WrongPassword,
+    #[error("keystore holds invalid key: {0}")]
+    InvalidKey(#[from] secp256k1::Error),
+    #[error("keystore password is missing, set either password file or password env var")]
+    MissingPassword,
+    #[error("failed to read keystore password from {name}: {source}")]
+    PasswordEnv {
+        name: String,
+        source: std::env::VarError,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SignedContractError {
+    #[error("signed contract differs from the prepared one")]
+    ContractMismatch,
+    #[error("unsupported signature algorithm {0}")]
+    UnsupportedSigAlgorithm(String),
+    #[error("invalid deployer public key: {0}")]
+    InvalidDeployer(#[from] secp256k1::Error),
+    #[error("signature does not match contract and deployer")]
+    InvalidSignature,
+    #[error("contract is signed by {actual} instead of {expected}")]
+    DeployerMismatch {
+        expected: WalletAddress,
+        actual: WalletAddress,
+    },
 }
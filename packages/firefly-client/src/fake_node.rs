@@ -0,0 +1,905 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use futures::{SinkExt, StreamExt};
+use poem::web::websocket::{Message, WebSocket};
+use poem::web::{Data, Json};
+use poem::{EndpointExt, IntoResponse, Route, handler};
+use prost::Message as _;
+use tokio::sync::{Notify, broadcast};
+use tokio::task::JoinHandle;
+use tonic::codegen::BoxStream;
+use tonic::transport::server::TcpIncoming;
+use tonic::{Request, Response, Status};
+
+use crate::errors::WriteNodeError;
+use crate::models::casper::v1::deploy_service_server::{DeployService, DeployServiceServer};
+use crate::models::casper::v1::propose_service_server::{ProposeService, ProposeServiceServer};
+use crate::models::casper::v1::{
+    BlockInfoResponse,
+    BlockResponse,
+    DeployResponse,
+    ExploratoryDeployResponse,
+    FindDeployResponse,
+    IsFinalizedResponse,
+    LastFinalizedBlockResponse,
+    ListeningNameDataPayload,
+    ListeningNameDataResponse,
+    PrivateNamePreviewPayload,
+    PrivateNamePreviewResponse,
+    ProposeResponse,
+    ProposeResultResponse,
+    RhoDataPayload,
+    RhoDataResponse,
+    StatusResponse,
+    block_info_response,
+    block_response,
+    deploy_response,
+    exploratory_deploy_response,
+    find_deploy_response,
+    is_finalized_response,
+    last_finalized_block_response,
+    listening_name_data_response,
+    private_name_preview_response,
+    propose_response,
+    propose_result_response,
+    rho_data_response,
+    status_response,
+};
+use crate::models::casper::{
+    BlockInfo,
+    BlockQuery,
+    BlocksQuery,
+    BlocksQueryByHeight,
+    DataAtNameByBlockQuery,
+    DataAtNameQuery,
+    DataWithBlockInfo,
+    DeployDataProto,
+    DeployInfo,
+    ExploratoryDeployQuery,
+    FindDeployQuery,
+    IsFinalizedQuery,
+    LastFinalizedBlockQuery,
+    LightBlockInfo,
+    PrivateNamePreviewQuery,
+    ProposeQuery,
+    ProposeResultQuery,
+    Status as NodeStatus,
+};
+use crate::models::rhoapi::expr::ExprInstance;
+use crate::models::rhoapi::{EList, EMap, ESet, ETuple, Expr, KeyValuePair, Par};
+use crate::models::servicemodelapi::ServiceError;
+use crate::rendering::Value;
+use crate::write_node_client::MAX_QUERY_DEPTH;
+use crate::{ReadNodeClient, WriteNodeClient};
+
+/// Execution result the fake node reports for a proposed deploy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeployExecution {
+    pub cost: u64,
+    pub errored: bool,
+}
+
+impl Default for DeployExecution {
+    fn default() -> Self {
+        Self {
+            cost: 1,
+            errored: false,
+        }
+    }
+}
+
+type ExploreHandler = Box<dyn Fn(&str) -> Option<Value> + Send + Sync>;
+type DeployHandler = Box<dyn Fn(&DeployDataProto) -> DeployExecution + Send + Sync>;
+
+struct State {
+    chain: Mutex<Chain>,
+    explore_handlers: RwLock<Vec<ExploreHandler>>,
+    deploy_handler: RwLock<Option<DeployHandler>>,
+    /// Data keyed by encoded name.
+    data_at_name: Mutex<HashMap<Vec<u8>, Vec<Value>>>,
+    async_propose: Mutex<Option<Result<String, ServiceError>>>,
+    dropped_deploy_responses: AtomicU64,
+    unavailable: AtomicBool,
+    events: broadcast::Sender<String>,
+    events_paused: AtomicBool,
+    disconnect_events: Notify,
+}
+
+struct Chain {
+    deploys: Vec<DeployDataProto>,
+    pending: Vec<DeployDataProto>,
+    blocks: Vec<BlockInfo>,
+    /// Channel data as of each block.
+    block_data: Vec<HashMap<Vec<u8>, Vec<Value>>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// In-process stand-in for a Firefly node: gRPC deploy and propose services,
+/// `/api/explore-deploy` and `/ws/events`, all bound to random localhost ports.
+///
+/// Every proposed block is finalized immediately. Exploratory deploys and channel data
+/// are answered from scripted responses, deploys are recorded as received.
+/// Servers are stopped when the node is dropped.
+pub struct FakeNode {
+    state: Arc<State>,
+    grpc_addr: SocketAddr,
+    http_addr: SocketAddr,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl FakeNode {
+    pub async fn start() -> std::io::Result<Self> {
+        let state = Arc::new(State {
+            chain: Mutex::new(Chain {
+                deploys: vec![],
+                pending: vec![],
+                blocks: vec![make_block(0, vec![])],
+                block_data: vec![HashMap::new()],
+            }),
+            explore_handlers: Default::default(),
+            deploy_handler: Default::default(),
+            data_at_name: Default::default(),
+            async_propose: Default::default(),
+            dropped_deploy_responses: AtomicU64::new(0),
+            unavailable: AtomicBool::new(false),
+            events: broadcast::Sender::new(32),
+            events_paused: AtomicBool::new(false),
+            disconnect_events: Notify::new(),
+        });
+
+        let grpc_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let grpc_addr = grpc_listener.local_addr()?;
+        let availability = {
+            let state = state.clone();
+            move |request: Request<()>| {
+                if state.unavailable.load(Ordering::Relaxed) {
+                    Err(Status::unavailable("node is down"))
+                } else {
+                    Ok(request)
+                }
+            }
+        };
+        let grpc_server = tonic::transport::Server::builder()
+            .add_service(DeployServiceServer::with_interceptor(
+                Service(state.clone()),
+                availability.clone(),
+            ))
+            .add_service(ProposeServiceServer::with_interceptor(
+                Service(state.clone()),
+                availability,
+            ))
+            .serve_with_incoming(TcpIncoming::from(grpc_listener));
+
+        let http_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let http_addr = http_listener.local_addr()?;
+        let http_server = poem::Server::new_with_acceptor(poem::listener::TcpAcceptor::from_tokio(
+            http_listener,
+        )?)
+        .run(
+            Route::new()
+                .at("/api/explore-deploy", poem::post(explore_deploy_http))
+                .at("/ws/events", poem::get(ws_events))
+                .data(state.clone()),
+        );
+
+        let tasks = vec![
+            tokio::spawn(async move {
+                let _ = grpc_server
+                    .await
+                    .inspect_err(|err| tracing::warn!("fake node grpc server: {err}"));
+            }),
+            tokio::spawn(async move {
+                let _ = http_server
+                    .await
+                    .inspect_err(|err| tracing::warn!("fake node http server: {err}"));
+            }),
+        ];
+
+        Ok(Self {
+            state,
+            grpc_addr,
+            http_addr,
+            tasks,
+        })
+    }
+
+    /// Url for both deploy and propose services.
+    pub fn grpc_url(&self) -> String {
+        format!("http://{}", self.grpc_addr)
+    }
+
+    /// Url of the HTTP api, as expected by [`ReadNodeClient::new`].
+    pub fn http_url(&self) -> String {
+        format!("http://{}", self.http_addr)
+    }
+
+    /// Url of the websocket api, as expected by [`crate::NodeEvents::new`].
+    pub fn ws_url(&self) -> String {
+        format!("ws://{}", self.http_addr)
+    }
+
+    pub async fn write_client(&self) -> Result<WriteNodeClient, WriteNodeError> {
+        WriteNodeClient::new(self.grpc_url(), self.grpc_url()).await
+    }
+
+    pub async fn read_client_grpc(&self) -> anyhow::Result<ReadNodeClient> {
+        ReadNodeClient::new_grpc(self.grpc_url()).await
+    }
+
+    pub fn read_client_http(&self) -> ReadNodeClient {
+        ReadNodeClient::new(self.http_url())
+    }
+
+    /// Adds responder for exploratory deploys, first one returning a value wins.
+    /// Terms without a response return no data.
+    pub fn on_explore_deploy<F>(&self, handler: F)
+    where
+        F: Fn(&str) -> Option<Value> + Send + Sync + 'static,
+    {
+        self.state
+            .explore_handlers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(handler));
+    }
+
+    /// Decides outcome of deploys included into proposed blocks, [`DeployExecution::default`] otherwise.
+    pub fn on_deploy<F>(&self, handler: F)
+    where
+        F: Fn(&DeployDataProto) -> DeployExecution + Send + Sync + 'static,
+    {
+        *self
+            .state
+            .deploy_handler
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Box::new(handler));
+    }
+
+    /// Data at `name` in the post-state of blocks proposed from now on,
+    /// as seen by `getDataAtName` and `listenForDataAtName`.
+    pub fn set_data_at_name(&self, name: impl Into<Par>, data: impl IntoIterator<Item = Value>) {
+        lock(&self.state.data_at_name)
+            .insert(name.into().encode_to_vec(), data.into_iter().collect());
+    }
+
+    /// Accepts next `count` deploys, but answers them with `UNAVAILABLE` as if the response was lost.
+    pub fn drop_deploy_responses(&self, count: u64) {
+        self.state
+            .dropped_deploy_responses
+            .store(count, Ordering::Relaxed);
+    }
+
+    /// Answers every gRPC call with `UNAVAILABLE` while `unavailable` is set, keeping deploy pool and blocks.
+    pub fn set_unavailable(&self, unavailable: bool) {
+        self.state.unavailable.store(unavailable, Ordering::Relaxed);
+    }
+
+    /// Drops websocket events of blocks proposed while `paused` is set, as if they were missed.
+    pub fn set_events_paused(&self, paused: bool) {
+        self.state.events_paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Closes all open websocket connections, clients are free to reconnect.
+    pub fn disconnect_events(&self) {
+        self.state.disconnect_events.notify_waiters();
+    }
+
+    /// All signed deploys received so far, in order of arrival.
+    pub fn deploys(&self) -> Vec<DeployDataProto> {
+        lock(&self.state.chain).deploys.clone()
+    }
+
+    /// All blocks including genesis, in order of block number.
+    pub fn blocks(&self) -> Vec<BlockInfo> {
+        lock(&self.state.chain).blocks.clone()
+    }
+}
+
+impl Drop for FakeNode {
+    fn drop(&mut self) {
+        self.tasks.iter().for_each(JoinHandle::abort);
+    }
+}
+
+fn make_block(number: u64, deploys: Vec<DeployInfo>) -> BlockInfo {
+    let hash = deploys
+        .iter()
+        .fold(
+            Blake2b::<U32>::new().chain_update(number.to_be_bytes()),
+            |hasher, deploy| hasher.chain_update(&deploy.sig),
+        )
+        .finalize();
+
+    BlockInfo {
+        block_info: Some(LightBlockInfo {
+            block_hash: hex::encode(hash),
+            block_number: number as _,
+            seq_num: number as _,
+            shard_id: "root".into(),
+            deploy_count: i32::try_from(deploys.len()).unwrap_or(i32::MAX),
+            ..Default::default()
+        }),
+        deploys,
+    }
+}
+
+fn light(block: &BlockInfo) -> LightBlockInfo {
+    block.block_info.clone().unwrap_or_default()
+}
+
+fn service_error(message: impl Into<String>) -> ServiceError {
+    ServiceError {
+        messages: vec![message.into()],
+    }
+}
+
+impl State {
+    fn explore(&self, term: &str) -> Option<Value> {
+        self.explore_handlers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .find_map(|handler| handler(term))
+    }
+
+    fn execute(&self, deploy: &DeployDataProto) -> DeployExecution {
+        self.deploy_handler
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_ref()
+            .map_or_else(DeployExecution::default, |handler| handler(deploy))
+    }
+
+    fn propose(&self) -> Result<String, ServiceError> {
+        let mut chain = lock(&self.chain);
+        if chain.pending.is_empty() {
+            return Err(service_error("Error: NoNewDeploys"));
+        }
+
+        let deploys: Vec<_> = std::mem::take(&mut chain.pending)
+            .into_iter()
+            .map(|deploy| {
+                let DeployExecution { cost, errored } = self.execute(&deploy);
+                DeployInfo {
+                    deployer: hex::encode(&deploy.deployer),
+                    term: deploy.term,
+                    timestamp: deploy.timestamp,
+                    sig: hex::encode(&deploy.sig),
+                    sig_algorithm: deploy.sig_algorithm,
+                    phlo_price: deploy.phlo_price,
+                    phlo_limit: deploy.phlo_limit,
+                    valid_after_block_number: deploy.valid_after_block_number,
+                    cost,
+                    errored,
+                    system_deploy_error: String::new(),
+                }
+            })
+            .collect();
+
+        let block = make_block(chain.blocks.len() as _, deploys);
+        let info = light(&block);
+        let payload = serde_json::json!({
+            "block-hash": info.block_hash,
+            "parent-hashes": chain
+                .blocks
+                .last()
+                .map(|parent| light(parent).block_hash)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            "creator": "",
+            "seq-num": info.seq_num,
+            "deploys": block.deploys.iter().map(|deploy| serde_json::json!({
+                "id": deploy.sig,
+                "cost": deploy.cost,
+                "deployer": deploy.deployer,
+                "errored": deploy.errored,
+            })).collect::<Vec<_>>(),
+        });
+        chain.blocks.push(block);
+        chain.block_data.push(lock(&self.data_at_name).clone());
+        drop(chain);
+
+        let events = if self.events_paused.load(Ordering::Relaxed) {
+            [].as_slice()
+        } else {
+            ["block-created", "block-added", "block-finalised"].as_slice()
+        };
+        for event in events {
+            let _ = self
+                .events
+                .send(serde_json::json!({ "event": event, "payload": payload }).to_string());
+        }
+
+        Ok(format!(
+            "Success! Block {} created and added.",
+            info.block_hash
+        ))
+    }
+
+    fn find_block(&self, hash: &str) -> Option<BlockInfo> {
+        lock(&self.chain)
+            .blocks
+            .iter()
+            .find(|block| light(block).block_hash == hash)
+            .cloned()
+    }
+
+    fn head(&self) -> BlockInfo {
+        lock(&self.chain).blocks.last().cloned().unwrap_or_default()
+    }
+}
+
+#[derive(Clone)]
+struct Service(Arc<State>);
+
+fn light_blocks(
+    blocks: impl IntoIterator<Item = BlockInfo>,
+) -> Response<BoxStream<BlockInfoResponse>> {
+    let responses: Vec<_> = blocks
+        .into_iter()
+        .map(|block| {
+            Ok(BlockInfoResponse {
+                message: Some(block_info_response::Message::BlockInfo(light(&block))),
+            })
+        })
+        .collect();
+
+    Response::new(Box::pin(futures::stream::iter(responses)))
+}
+
+#[tonic::async_trait]
+impl DeployService for Service {
+    async fn do_deploy(
+        &self,
+        request: Request<DeployDataProto>,
+    ) -> Result<Response<DeployResponse>, Status> {
+        let deploy = request.into_inner();
+        let deploy_id = hex::encode(&deploy.sig);
+
+        {
+            // like the node, keeps deploys by signature and refuses to take one in twice
+            let mut chain = lock(&self.0.chain);
+            if chain.deploys.iter().any(|known| known.sig == deploy.sig) {
+                return Ok(Response::new(DeployResponse {
+                    message: Some(deploy_response::Message::Error(service_error(format!(
+                        "Duplicate deploy {deploy_id}"
+                    )))),
+                }));
+            }
+            chain.deploys.push(deploy.clone());
+            chain.pending.push(deploy);
+        }
+
+        let dropped = self.0.dropped_deploy_responses.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |count| count.checked_sub(1),
+        );
+        if dropped.is_ok() {
+            return Err(Status::unavailable("response lost"));
+        }
+
+        Ok(Response::new(DeployResponse {
+            message: Some(deploy_response::Message::Result(format!(
+                "Success! DeployId is: {deploy_id}"
+            ))),
+        }))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<BlockQuery>,
+    ) -> Result<Response<BlockResponse>, Status> {
+        let hash = request.into_inner().hash;
+        let message = self.0.find_block(&hash).map_or_else(
+            || {
+                block_response::Message::Error(service_error(format!(
+                    "Error: Failure to find block with hash: {hash}"
+                )))
+            },
+            block_response::Message::BlockInfo,
+        );
+
+        Ok(Response::new(BlockResponse {
+            message: Some(message),
+        }))
+    }
+
+    async fn show_main_chain(
+        &self,
+        request: Request<BlocksQuery>,
+    ) -> Result<Response<BoxStream<BlockInfoResponse>>, Status> {
+        self.get_blocks(request).await
+    }
+
+    async fn get_blocks(
+        &self,
+        request: Request<BlocksQuery>,
+    ) -> Result<Response<BoxStream<BlockInfoResponse>>, Status> {
+        let depth = request.into_inner().depth.max(0) as usize;
+        let blocks = lock(&self.0.chain).blocks.clone();
+
+        Ok(light_blocks(blocks.into_iter().rev().take(depth)))
+    }
+
+    async fn get_blocks_by_heights(
+        &self,
+        request: Request<BlocksQueryByHeight>,
+    ) -> Result<Response<BoxStream<BlockInfoResponse>>, Status> {
+        let query = request.into_inner();
+        // like the node's default api-max-blocks-limit
+        if query
+            .end_block_number
+            .saturating_sub(query.start_block_number)
+            >= i64::from(MAX_QUERY_DEPTH)
+        {
+            return Err(Status::invalid_argument("too many blocks requested"));
+        }
+        let blocks = lock(&self.0.chain).blocks.clone();
+
+        Ok(light_blocks(blocks.into_iter().filter(|block| {
+            (query.start_block_number..=query.end_block_number).contains(&light(block).block_number)
+        })))
+    }
+
+    async fn get_data_at_name(
+        &self,
+        request: Request<DataAtNameByBlockQuery>,
+    ) -> Result<Response<RhoDataResponse>, Status> {
+        let query = request.into_inner();
+        let name = query.par.unwrap_or_default().encode_to_vec();
+
+        let chain = lock(&self.0.chain);
+        let message = chain
+            .blocks
+            .iter()
+            .zip(&chain.block_data)
+            .find(|(block, _)| light(block).block_hash == query.block_hash)
+            .map_or_else(
+                || {
+                    rho_data_response::Message::Error(service_error(format!(
+                        "Error: Failure to find block with hash: {}",
+                        query.block_hash
+                    )))
+                },
+                |(block, data)| {
+                    rho_data_response::Message::Payload(RhoDataPayload {
+                        par: data
+                            .get(&name)
+                            .into_iter()
+                            .flatten()
+                            .cloned()
+                            .map(value_to_par)
+                            .collect(),
+                        block: Some(light(block)),
+                    })
+                },
+            );
+        drop(chain);
+
+        Ok(Response::new(RhoDataResponse {
+            message: Some(message),
+        }))
+    }
+
+    async fn listen_for_data_at_name(
+        &self,
+        request: Request<DataAtNameQuery>,
+    ) -> Result<Response<ListeningNameDataResponse>, Status> {
+        let query = request.into_inner();
+        let name = query.name.unwrap_or_default().encode_to_vec();
+
+        let chain = lock(&self.0.chain);
+        let block_info: Vec<_> = chain
+            .blocks
+            .iter()
+            .zip(&chain.block_data)
+            .rev()
+            .take(query.depth.max(0) as usize)
+            .map(|(block, data)| DataWithBlockInfo {
+                post_block_data: data
+                    .get(&name)
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .map(value_to_par)
+                    .collect(),
+                block: Some(light(block)),
+            })
+            .collect();
+        drop(chain);
+
+        Ok(Response::new(ListeningNameDataResponse {
+            message: Some(listening_name_data_response::Message::Payload(
+                ListeningNameDataPayload {
+                    length: i32::try_from(block_info.len()).unwrap_or(i32::MAX),
+                    block_info,
+                },
+            )),
+        }))
+    }
+
+    async fn find_deploy(
+        &self,
+        request: Request<FindDeployQuery>,
+    ) -> Result<Response<FindDeployResponse>, Status> {
+        let deploy_id = hex::encode(request.into_inner().deploy_id);
+        let block = lock(&self.0.chain)
+            .blocks
+            .iter()
+            .find(|block| block.deploys.iter().any(|deploy| deploy.sig == deploy_id))
+            .map(light);
+
+        let message = block.map_or_else(
+            || {
+                find_deploy_response::Message::Error(service_error(format!(
+                    "Couldn't find block containing deploy with id: {deploy_id}"
+                )))
+            },
+            find_deploy_response::Message::BlockInfo,
+        );
+
+        Ok(Response::new(FindDeployResponse {
+            message: Some(message),
+        }))
+    }
+
+    async fn last_finalized_block(
+        &self,
+        _request: Request<LastFinalizedBlockQuery>,
+    ) -> Result<Response<LastFinalizedBlockResponse>, Status> {
+        Ok(Response::new(LastFinalizedBlockResponse {
+            message: Some(last_finalized_block_response::Message::BlockInfo(
+                self.0.head(),
+            )),
+        }))
+    }
+
+    async fn preview_private_names(
+        &self,
+        request: Request<PrivateNamePreviewQuery>,
+    ) -> Result<Response<PrivateNamePreviewResponse>, Status> {
+        // ids only have to be distinct per deploy here, the order real nodes create names in is
+        // covered by the api tests running against them
+        let query = request.into_inner();
+        let ids = (0..query.name_qty.max(0))
+            .map(|index| {
+                Blake2b::<U32>::new()
+                    .chain_update(&query.user)
+                    .chain_update(query.timestamp.to_be_bytes())
+                    .chain_update(index.to_be_bytes())
+                    .finalize()
+                    .to_vec()
+            })
+            .collect();
+
+        Ok(Response::new(PrivateNamePreviewResponse {
+            message: Some(private_name_preview_response::Message::Payload(
+                PrivateNamePreviewPayload { ids },
+            )),
+        }))
+    }
+
+    async fn status(&self, _request: Request<()>) -> Result<Response<StatusResponse>, Status> {
+        Ok(Response::new(StatusResponse {
+            message: Some(status_response::Message::Status(NodeStatus {
+                shard_id: "root".to_owned(),
+                ..Default::default()
+            })),
+        }))
+    }
+
+    async fn is_finalized(
+        &self,
+        request: Request<IsFinalizedQuery>,
+    ) -> Result<Response<IsFinalizedResponse>, Status> {
+        let is_finalized = self.0.find_block(&request.into_inner().hash).is_some();
+
+        Ok(Response::new(IsFinalizedResponse {
+            message: Some(is_finalized_response::Message::IsFinalized(is_finalized)),
+        }))
+    }
+
+    async fn exploratory_deploy(
+        &self,
+        request: Request<ExploratoryDeployQuery>,
+    ) -> Result<Response<ExploratoryDeployResponse>, Status> {
+        let value = self.0.explore(&request.into_inner().term);
+
+        Ok(Response::new(ExploratoryDeployResponse {
+            message: Some(exploratory_deploy_response::Message::Result(
+                DataWithBlockInfo {
+                    post_block_data: value.map(value_to_par).into_iter().collect(),
+                    block: Some(light(&self.0.head())),
+                },
+            )),
+        }))
+    }
+}
+
+#[tonic::async_trait]
+impl ProposeService for Service {
+    async fn propose(
+        &self,
+        request: Request<ProposeQuery>,
+    ) -> Result<Response<ProposeResponse>, Status> {
+        let result = self.0.propose();
+
+        let message = if request.into_inner().is_async {
+            *lock(&self.0.async_propose) = Some(result);
+            propose_response::Message::Result("Propose started".to_owned())
+        } else {
+            match result {
+                Ok(result) => propose_response::Message::Result(result),
+                Err(err) => propose_response::Message::Error(err),
+            }
+        };
+
+        Ok(Response::new(ProposeResponse {
+            message: Some(message),
+        }))
+    }
+
+    async fn propose_result(
+        &self,
+        _request: Request<ProposeResultQuery>,
+    ) -> Result<Response<ProposeResultResponse>, Status> {
+        let result = lock(&self.0.async_propose)
+            .take()
+            .unwrap_or_else(|| Err(service_error("Error: no propose in progress")));
+
+        let message = match result {
+            Ok(result) => propose_result_response::Message::Result(result),
+            Err(err) => propose_result_response::Message::Error(err),
+        };
+
+        Ok(Response::new(ProposeResultResponse {
+            message: Some(message),
+        }))
+    }
+}
+
+#[handler]
+#[allow(clippy::needless_pass_by_value)]
+fn explore_deploy_http(Data(state): Data<&Arc<State>>, term: String) -> Json<serde_json::Value> {
+    let expr: Vec<_> = state
+        .explore(&term)
+        .map(value_to_json)
+        .into_iter()
+        .collect();
+
+    Json(serde_json::json!({
+        "expr": expr,
+        "block": { "blockHash": light(&state.head()).block_hash },
+    }))
+}
+
+#[handler]
+fn ws_events(ws: WebSocket, Data(state): Data<&Arc<State>>) -> impl IntoResponse {
+    let mut rx = state.events.subscribe();
+    let state = state.clone();
+
+    ws.on_upgrade(move |socket| async move {
+        let (mut sink, mut stream) = socket.split();
+
+        let forward = async {
+            if sink
+                .send(Message::Text(r#"{"event":"started"}"#.into()))
+                .await
+                .is_err()
+            {
+                return;
+            }
+
+            loop {
+                match rx.recv().await {
+                    Ok(event) => {
+                        if sink.send(Message::Text(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => return,
+                }
+            }
+        };
+
+        tokio::select! {
+            () = forward => {},
+            _ = async { while stream.next().await.is_some() {} } => {},
+            () = state.disconnect_events.notified() => {},
+        }
+        let _ = sink.close().await;
+    })
+}
+
+fn par(expr: ExprInstance) -> Par {
+    Par {
+        exprs: vec![Expr {
+            expr_instance: Some(expr),
+        }],
+        ..Default::default()
+    }
+}
+
+/// # Panics
+///
+/// Panics on [`Value::Inline`], since it's code rather than data.
+fn value_to_par(value: Value) -> Par {
+    let pars = |values: Vec<Value>| values.into_iter().map(value_to_par).collect();
+
+    match value {
+        Value::Nil => Par::default(),
+        Value::Bool(value) => par(ExprInstance::GBool(value)),
+        Value::Int(value) => par(ExprInstance::GInt(value)),
+        Value::String(value) => par(ExprInstance::GString(value)),
+        Value::Bytes(value) => par(ExprInstance::GByteArray(value)),
+        Value::Uri(value) => par(ExprInstance::GUri(value)),
+        Value::Tuple(values) => par(ExprInstance::ETupleBody(ETuple {
+            ps: pars(values),
+            ..Default::default()
+        })),
+        Value::List(values) => par(ExprInstance::EListBody(EList {
+            ps: pars(values),
+            ..Default::default()
+        })),
+        Value::Set(values) => par(ExprInstance::ESetBody(ESet {
+            ps: pars(values.into_iter().collect()),
+            ..Default::default()
+        })),
+        Value::Map(entries) => par(ExprInstance::EMapBody(EMap {
+            kvs: entries
+                .into_iter()
+                .map(|(key, value)| KeyValuePair {
+                    key: Some(par(ExprInstance::GString(key))),
+                    value: Some(value_to_par(value)),
+                })
+                .collect(),
+            ..Default::default()
+        })),
+        Value::Inline(code) => panic!("fake node can't evaluate inline code: {code}"),
+    }
+}
+
+/// Encodes value the way `/api/explore-deploy` does.
+///
+/// # Panics
+///
+/// Panics on [`Value::Inline`], since it's code rather than data.
+fn value_to_json(value: Value) -> serde_json::Value {
+    let data = |kind: &str, data: serde_json::Value| {
+        serde_json::Value::Object(
+            std::iter::once((kind.to_owned(), serde_json::json!({ "data": data }))).collect(),
+        )
+    };
+    let items = |values: Vec<Value>| values.into_iter().map(value_to_json).collect();
+
+    match value {
+        Value::Nil => serde_json::json!({ "ExprNil": {} }),
+        Value::Bool(value) => data("ExprBool", value.into()),
+        Value::Int(value) => data("ExprInt", value.into()),
+        Value::String(value) => data("ExprString", value.into()),
+        Value::Bytes(value) => data("ExprBytes", hex::encode(value).into()),
+        Value::Uri(value) => data("ExprUri", value.into()),
+        Value::Tuple(values) => data("ExprTuple", items(values)),
+        Value::List(values) => data("ExprList", items(values)),
+        Value::Set(values) => data("ExprSet", items(values.into_iter().collect())),
+        Value::Map(entries) => data(
+            "ExprMap",
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, value_to_json(value)))
+                .collect::<serde_json::Map<_, _>>()
+                .into(),
+        ),
+        Value::Inline(code) => panic!("fake node can't evaluate inline code: {code}"),
+    }
+}
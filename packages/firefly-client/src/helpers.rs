@@ -1,17 +1,22 @@
 use std::collections::HashMap;
 
 use anyhow::{Context, anyhow};
-use blake2::digest::consts::U32;
-use blake2::{Blake2b, Digest};
 use chrono::{DateTime, Utc};
-use prost::Message as _;
-use secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+pub use firefly_client_macros::FromExpr;
+use secp256k1::{PublicKey, SecretKey};
+use uuid::Uuid;
 
 use crate::models::rhoapi;
 use crate::models::rhoapi::expr::ExprInstance;
 
 pub trait FromExpr: Sized {
     fn from(val: ExprInstance) -> anyhow::Result<Self>;
+
+    /// Decodes whole process. Overridden by types that are not represented
+    /// by a single expression, like [`Option`] or unforgeable names.
+    fn from_par(par: rhoapi::Par) -> anyhow::Result<Self> {
+        expr_instance(par).and_then(Self::from)
+    }
 }
 
 impl FromExpr for String {
@@ -23,21 +28,72 @@ impl FromExpr for String {
     }
 }
 
+impl FromExpr for Uuid {
+    fn from(val: ExprInstance) -> anyhow::Result<Self> {
+        let value: String = FromExpr::from(val)?;
+        value.parse().map_err(Into::into)
+    }
+}
+
+impl FromExpr for bool {
+    fn from(val: ExprInstance) -> anyhow::Result<Self> {
+        match val {
+            ExprInstance::GBool(value) => Ok(value),
+            other => Err(anyhow!("unexpected expr type: {other:?} expected GBool")),
+        }
+    }
+}
+
+impl FromExpr for i64 {
+    fn from(val: ExprInstance) -> anyhow::Result<Self> {
+        match val {
+            ExprInstance::GInt(value) => Ok(value),
+            other => Err(anyhow!("unexpected expr type: {other:?} expected GInt")),
+        }
+    }
+}
+
+macro_rules! impl_from_expr_for_int {
+    ($($ty:ty),+) => {
+        $(
+            impl FromExpr for $ty {
+                fn from(val: ExprInstance) -> anyhow::Result<Self> {
+                    let value: i64 = FromExpr::from(val)?;
+                    value.try_into().with_context(|| {
+                        format!("{value} is out of range for {}", stringify!($ty))
+                    })
+                }
+            }
+        )+
+    };
+}
+
+impl_from_expr_for_int!(i8, i16, i32, u16, u32, u64);
+
+impl<T> FromExpr for Option<T>
+where
+    T: FromExpr,
+{
+    fn from(val: ExprInstance) -> anyhow::Result<Self> {
+        T::from(val).map(Some)
+    }
+
+    fn from_par(par: rhoapi::Par) -> anyhow::Result<Self> {
+        if par == rhoapi::Par::default() {
+            return Ok(None);
+        }
+
+        T::from_par(par).map(Some)
+    }
+}
+
 impl<T> FromExpr for Vec<T>
 where
     T: FromExpr,
 {
     fn from(val: ExprInstance) -> anyhow::Result<Self> {
         match val {
-            ExprInstance::EListBody(list) => list
-                .ps
-                .into_iter()
-                .map(|par| {
-                    let expr = par.exprs.into_iter().next().context("missing exprs")?;
-                    let expr = expr.expr_instance.context("missing expr_instance")?;
-                    T::from(expr)
-                })
-                .collect(),
+            ExprInstance::EListBody(list) => list.ps.into_iter().map(T::from_par).collect(),
             other => Err(anyhow!(
                 "unexpected expr type: {other:?} expected EListBody"
             )),
@@ -50,30 +106,10 @@ where
     T: FromExpr,
 {
     fn from(val: ExprInstance) -> anyhow::Result<Self> {
-        match val {
-            ExprInstance::EMapBody(map) => map
-                .kvs
-                .into_iter()
-                .map(|pair| {
-                    let key = pair
-                        .key
-                        .and_then(|key| key.exprs.into_iter().next())
-                        .and_then(|expr| expr.expr_instance)
-                        .context("missing key")?;
-                    let key = FromExpr::from(key)?;
-
-                    let value = pair
-                        .value
-                        .and_then(|value| value.exprs.into_iter().next())
-                        .and_then(|expr| expr.expr_instance)
-                        .context("missing value")?;
-                    let value = FromExpr::from(value)?;
-
-                    Ok((key, value))
-                })
-                .collect(),
-            other => Err(anyhow!("unexpected expr type: {other:?} expected EMapBody")),
-        }
+        map_entries(val)?
+            .into_iter()
+            .map(|(key, value)| Ok((key, T::from_par(value)?)))
+            .collect()
     }
 }
 
@@ -88,6 +124,189 @@ impl FromExpr for Vec<u8> {
     }
 }
 
+/// Data in the shape [`crate::deserializer::ParDeserializer`] gives it, with bytes as hex strings.
+impl FromExpr for serde_json::Value {
+    fn from(val: ExprInstance) -> anyhow::Result<Self> {
+        Self::from_par(rhoapi::Par {
+            exprs: vec![rhoapi::Expr {
+                expr_instance: Some(val),
+            }],
+            ..Default::default()
+        })
+    }
+
+    fn from_par(par: rhoapi::Par) -> anyhow::Result<Self> {
+        crate::deserializer::from_par(par)
+            .map(|JsonValue(value)| value)
+            .map_err(Into::into)
+    }
+}
+
+/// [`serde_json::Value`] taking bytes, which JSON has no type for, as hex strings.
+struct JsonValue(serde_json::Value);
+
+impl<'de> serde::Deserialize<'de> for JsonValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(JsonValueVisitor)
+    }
+}
+
+struct JsonValueVisitor;
+
+impl<'de> serde::de::Visitor<'de> for JsonValueVisitor {
+    type Value = JsonValue;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(JsonValue(v.into()))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(JsonValue(v.into()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(JsonValue(v.into()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(JsonValue(v.into()))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(JsonValue(hex::encode(v).into()))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(JsonValue(serde_json::Value::Null))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(JsonValue(serde_json::Value::Null))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::Deserialize::deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(JsonValue(value)) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(JsonValue(values.into()))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        let mut entries = serde_json::Map::new();
+        while let Some((key, JsonValue(value))) = map.next_entry::<String, JsonValue>()? {
+            entries.insert(key, value);
+        }
+        Ok(JsonValue(entries.into()))
+    }
+}
+
+#[doc(hidden)]
+pub fn expr_instance(par: rhoapi::Par) -> anyhow::Result<ExprInstance> {
+    let expr = par.exprs.into_iter().next().context("missing exprs")?;
+    expr.expr_instance.context("missing expr_instance")
+}
+
+/// Splits `EMapBody` with string keys into raw entries.
+#[doc(hidden)]
+pub fn map_entries(val: ExprInstance) -> anyhow::Result<HashMap<String, rhoapi::Par>> {
+    match val {
+        ExprInstance::EMapBody(map) => map
+            .kvs
+            .into_iter()
+            .map(|pair| {
+                let key = pair.key.context("missing key")?;
+                let key = FromExpr::from_par(key)?;
+                let value = pair.value.context("missing value")?;
+
+                Ok((key, value))
+            })
+            .collect(),
+        other => Err(anyhow!("unexpected expr type: {other:?} expected EMapBody")),
+    }
+}
+
+/// Splits `ETupleBody` into raw elements checking its arity.
+#[doc(hidden)]
+pub fn tuple_elements(val: ExprInstance, len: usize) -> anyhow::Result<Vec<rhoapi::Par>> {
+    match val {
+        ExprInstance::ETupleBody(tuple) if tuple.ps.len() == len => Ok(tuple.ps),
+        ExprInstance::ETupleBody(tuple) => Err(anyhow!(
+            "unexpected tuple length: {} expected {len}",
+            tuple.ps.len()
+        )),
+        other => Err(anyhow!(
+            "unexpected expr type: {other:?} expected ETupleBody"
+        )),
+    }
+}
+
+/// Decodes tuple element which arity was already checked by [`tuple_elements`].
+#[doc(hidden)]
+pub fn take_element<T>(
+    elements: &mut impl Iterator<Item = rhoapi::Par>,
+    index: usize,
+) -> anyhow::Result<T>
+where
+    T: FromExpr,
+{
+    T::from_par(elements.next().unwrap_or_default())
+        .with_context(|| format!("invalid element {index}"))
+}
+
+/// Splits enum variant encoded either as bare `"Variant"` string
+/// or as `("Variant", payload)` tuple.
+#[doc(hidden)]
+pub fn enum_variant(val: ExprInstance) -> anyhow::Result<(String, Option<rhoapi::Par>)> {
+    match val {
+        ExprInstance::GString(tag) => Ok((tag, None)),
+        val @ ExprInstance::ETupleBody(_) => {
+            let mut elements = tuple_elements(val, 2)?.into_iter();
+            let tag = take_element(&mut elements, 0)?;
+            Ok((tag, elements.next()))
+        }
+        other => Err(anyhow!(
+            "unexpected expr type: {other:?} expected GString or ETupleBody"
+        )),
+    }
+}
+
+/// Decodes struct field, absent key is treated as `Nil`.
+#[doc(hidden)]
+pub fn take_field<T>(entries: &mut HashMap<String, rhoapi::Par>, name: &str) -> anyhow::Result<T>
+where
+    T: FromExpr,
+{
+    entries.remove(name).map_or_else(
+        || T::from_par(Default::default()).map_err(|_| anyhow!("missing field `{name}`")),
+        |par| T::from_par(par).with_context(|| format!("invalid field `{name}`")),
+    )
+}
+
+pub mod _dependencies {
+    pub use anyhow;
+}
+
 pub trait ShortHex {
     fn short_hex(&self, length: usize) -> String;
 }
@@ -112,53 +331,14 @@ pub fn insert_signed_signature(
     deployer: &PublicKey,
     version: i64,
 ) -> Vec<u8> {
-    let par = rhoapi::Par {
-        exprs: vec![rhoapi::Expr {
-            expr_instance: Some(rhoapi::expr::ExprInstance::ETupleBody(rhoapi::ETuple {
-                ps: vec![
-                    rhoapi::Par {
-                        exprs: vec![rhoapi::Expr {
-                            expr_instance: Some(rhoapi::expr::ExprInstance::GInt(
-                                timestamp.timestamp_millis(),
-                            )),
-                        }],
-                        ..Default::default()
-                    },
-                    rhoapi::Par {
-                        exprs: vec![rhoapi::Expr {
-                            expr_instance: Some(rhoapi::expr::ExprInstance::GByteArray(
-                                deployer.serialize_uncompressed().into(),
-                            )),
-                        }],
-                        ..Default::default()
-                    },
-                    rhoapi::Par {
-                        exprs: vec![rhoapi::Expr {
-                            expr_instance: Some(rhoapi::expr::ExprInstance::GInt(version)),
-                        }],
-                        ..Default::default()
-                    },
-                ],
-                ..Default::default()
-            })),
-        }],
-        ..Default::default()
-    }
-    .encode_to_vec();
-
-    let hash = Blake2b::<U32>::new().chain_update(par).finalize();
-
-    Secp256k1::new()
-        .sign_ecdsa(Message::from_digest(hash.into()), key)
-        .serialize_der()
-        .to_vec()
+    firefly_signing::insert_signed_signature(key, timestamp.timestamp_millis(), deployer, version)
 }
 
 #[test]
 fn test_insert_signed_signature() {
     use std::str::FromStr;
 
-    let secp = Secp256k1::new();
+    let secp = secp256k1::Secp256k1::new();
     let timestamp = DateTime::from_timestamp_millis(1_559_156_356_769).unwrap();
     let secret_key =
         SecretKey::from_str("f450b26bac63e5dd9343cd46f5fae1986d367a893cd21eedd98a4cb3ac699abc")
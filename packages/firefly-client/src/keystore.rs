@@ -0,0 +1,299 @@
+//! Secret keys in password encrypted keystore files, in Ethereum v3 JSON format.
+
+use std::path::{Path, PathBuf};
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use secp256k1::SecretKey;
+use secp256k1::rand::{self, RngCore};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use subtle::ConstantTimeEq;
+use uuid::Uuid;
+
+use crate::errors::KeystoreError;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+const VERSION: u8 = 3;
+const CIPHER: &str = "aes-128-ctr";
+const PRF: &str = "hmac-sha256";
+const DERIVED_KEY_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore {
+    #[serde(alias = "Crypto")]
+    pub crypto: Crypto,
+    pub id: Uuid,
+    pub version: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Crypto {
+    pub cipher: String,
+    pub cipherparams: CipherParams,
+    #[serde(with = "hex")]
+    pub ciphertext: Vec<u8>,
+    #[serde(flatten)]
+    pub kdf: Kdf,
+    #[serde(with = "hex")]
+    pub mac: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CipherParams {
+    #[serde(with = "hex")]
+    pub iv: Vec<u8>,
+}
+
+/// Function deriving encryption key from password.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum Kdf {
+    Scrypt {
+        dklen: usize,
+        n: u32,
+        r: u32,
+        p: u32,
+        #[serde(with = "hex")]
+        salt: Vec<u8>,
+    },
+    Pbkdf2 {
+        c: u32,
+        dklen: usize,
+        prf: String,
+        #[serde(with = "hex")]
+        salt: Vec<u8>,
+    },
+}
+
+impl Kdf {
+    /// Scrypt with parameters geth uses for new keys and a fresh salt.
+    pub fn scrypt() -> Self {
+        Self::Scrypt {
+            dklen: DERIVED_KEY_LEN,
+            n: 1 << 18,
+            r: 8,
+            p: 1,
+            salt: random_bytes::<32>().to_vec(),
+        }
+    }
+
+    fn derive(&self, password: &[u8]) -> Result<Vec<u8>, KeystoreError> {
+        match self {
+            Self::Scrypt {
+                dklen,
+                n,
+                r,
+                p,
+                salt,
+            } => {
+                if !n.is_power_of_two() {
+                    return Err(KeystoreError::InvalidParams(format!(
+                        "scrypt n {n} is not a power of two"
+                    )));
+                }
+                let log_n = u8::try_from(n.trailing_zeros()).unwrap_or(u8::MAX);
+                let params = scrypt::Params::new(log_n, *r, *p, *dklen)
+                    .map_err(|err| KeystoreError::InvalidParams(err.to_string()))?;
+
+                let mut key = vec![0; *dklen];
+                scrypt::scrypt(password, salt, &params, &mut key)
+                    .map_err(|err| KeystoreError::InvalidParams(err.to_string()))?;
+                Ok(key)
+            }
+            Self::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            } => {
+                if prf != PRF {
+                    return Err(KeystoreError::Unsupported(format!("prf {prf}")));
+                }
+
+                let mut key = vec![0; *dklen];
+                pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password, salt, *c, &mut key);
+                Ok(key)
+            }
+        }
+    }
+
+    const fn dklen(&self) -> usize {
+        match self {
+            Self::Scrypt { dklen, .. } | Self::Pbkdf2 { dklen, .. } => *dklen,
+        }
+    }
+}
+
+impl Keystore {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeystoreError> {
+        let json = read(path.as_ref())?;
+        serde_json::from_str(&json).map_err(Into::into)
+    }
+
+    pub fn encrypt(key: &SecretKey, password: &[u8], kdf: Kdf) -> Result<Self, KeystoreError> {
+        if kdf.dklen() < DERIVED_KEY_LEN {
+            return Err(KeystoreError::InvalidParams(format!(
+                "dklen must be at least {DERIVED_KEY_LEN}"
+            )));
+        }
+        let derived_key = kdf.derive(password)?;
+        let iv = random_bytes::<16>().to_vec();
+
+        let mut ciphertext = key.secret_bytes().to_vec();
+        Aes128Ctr::new_from_slices(&derived_key[..16], &iv)
+            .map_err(|err| KeystoreError::InvalidParams(err.to_string()))?
+            .apply_keystream(&mut ciphertext);
+
+        Ok(Self {
+            crypto: Crypto {
+                cipher: CIPHER.to_owned(),
+                cipherparams: CipherParams { iv },
+                mac: mac(&derived_key, &ciphertext),
+                ciphertext,
+                kdf,
+            },
+            id: Uuid::new_v4(),
+            version: VERSION,
+        })
+    }
+
+    pub fn decrypt(&self, password: &[u8]) -> Result<SecretKey, KeystoreError> {
+        if self.version != VERSION {
+            return Err(KeystoreError::Unsupported(format!(
+                "version {}",
+                self.version
+            )));
+        }
+        if self.crypto.cipher != CIPHER {
+            return Err(KeystoreError::Unsupported(format!(
+                "cipher {}",
+                self.crypto.cipher
+            )));
+        }
+        if self.crypto.kdf.dklen() < DERIVED_KEY_LEN {
+            return Err(KeystoreError::InvalidParams(format!(
+                "dklen must be at least {DERIVED_KEY_LEN}"
+            )));
+        }
+
+        let derived_key = self.crypto.kdf.derive(password)?;
+        let mac = mac(&derived_key, &self.crypto.ciphertext);
+        if !bool::from(mac.ct_eq(&self.crypto.mac)) {
+            return Err(KeystoreError::WrongPassword);
+        }
+
+        let mut key = self.crypto.ciphertext.clone();
+        Aes128Ctr::new_from_slices(&derived_key[..16], &self.crypto.cipherparams.iv)
+            .map_err(|err| KeystoreError::InvalidParams(err.to_string()))?
+            .apply_keystream(&mut key);
+
+        let key: [u8; 32] = key
+            .try_into()
+            .map_err(|_| KeystoreError::InvalidParams("key must be 32 bytes".to_owned()))?;
+        SecretKey::from_byte_array(key).map_err(Into::into)
+    }
+}
+
+/// Secret key given either in hex or as keystore file with password kept elsewhere.
+///
+/// Deserializes from a hex string or from a map with `keystore` path
+/// and either `password_file` path or `password_env` variable name.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum KeySource {
+    Hex(SecretKey),
+    Keystore {
+        keystore: PathBuf,
+        password_file: Option<PathBuf>,
+        password_env: Option<String>,
+    },
+}
+
+impl KeySource {
+    pub fn load(self) -> Result<SecretKey, KeystoreError> {
+        let (keystore, password_file, password_env) = match self {
+            Self::Hex(key) => return Ok(key),
+            Self::Keystore {
+                keystore,
+                password_file,
+                password_env,
+            } => (keystore, password_file, password_env),
+        };
+
+        let password = match (password_file, password_env) {
+            (Some(path), _) => {
+                let password = read(&path)?;
+                // editors and `echo` leave trailing newline
+                password
+                    .strip_suffix('\n')
+                    .map_or(password.as_str(), |password| {
+                        password.strip_suffix('\r').unwrap_or(password)
+                    })
+                    .to_owned()
+            }
+            (None, Some(name)) => std::env::var(&name)
+                .map_err(|source| KeystoreError::PasswordEnv { name, source })?,
+            (None, None) => return Err(KeystoreError::MissingPassword),
+        };
+
+        Keystore::load(keystore)?.decrypt(password.as_bytes())
+    }
+}
+
+/// Command line arguments selecting a wallet key, meant to be flattened into a [`clap::Parser`].
+#[cfg(feature = "clap")]
+#[derive(Debug, Clone, clap::Args)]
+pub struct WalletKeyArgs {
+    /// Wallet key in hex format
+    #[arg(long, required_unless_present = "wallet_keystore")]
+    pub wallet_key: Option<SecretKey>,
+
+    /// Wallet keystore file, used instead of --wallet-key
+    #[arg(long, conflicts_with = "wallet_key", requires = "password")]
+    pub wallet_keystore: Option<PathBuf>,
+
+    /// File with wallet keystore password
+    #[arg(long, group = "password")]
+    pub password_file: Option<PathBuf>,
+
+    /// Environment variable with wallet keystore password
+    #[arg(long, group = "password")]
+    pub password_env: Option<String>,
+}
+
+#[cfg(feature = "clap")]
+impl WalletKeyArgs {
+    pub fn load(self) -> Result<SecretKey, KeystoreError> {
+        let source = match (self.wallet_key, self.wallet_keystore) {
+            (Some(key), _) => KeySource::Hex(key),
+            (None, keystore) => KeySource::Keystore {
+                keystore: keystore.unwrap_or_default(),
+                password_file: self.password_file,
+                password_env: self.password_env,
+            },
+        };
+        source.load()
+    }
+}
+
+fn mac(derived_key: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    Keccak256::new()
+        .chain_update(&derived_key[16..32])
+        .chain_update(ciphertext)
+        .finalize()
+        .to_vec()
+}
+
+fn random_bytes<const N: usize>() -> [u8; N] {
+    let mut bytes = [0; N];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes
+}
+
+fn read(path: &Path) -> Result<String, KeystoreError> {
+    std::fs::read_to_string(path).map_err(|source| KeystoreError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
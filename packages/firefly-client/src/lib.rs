@@ -1,13 +1,21 @@
+pub mod block_explorer;
 mod communication_service;
+pub mod deserializer;
 pub mod errors;
+#[cfg(feature = "test-support")]
+pub mod fake_node;
 pub mod helpers;
+pub mod keystore;
 pub mod models;
 pub mod node_events;
+pub mod proposer;
 mod read_node_client;
 pub mod rendering;
 mod write_node_client;
 
+pub use block_explorer::BlockExplorer;
 pub use communication_service::CommunicationService;
 pub use node_events::NodeEvents;
+pub use proposer::Proposer;
 pub use read_node_client::ReadNodeClient;
 pub use write_node_client::WriteNodeClient;
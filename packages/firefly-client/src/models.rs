@@ -12,7 +12,8 @@ use secp256k1::PublicKey;
 use serde::{Deserialize, Deserializer, Serialize, de};
 use thiserror::Error;
 
-use crate::helpers::ShortHex;
+use crate::errors::SignedContractError;
+use crate::helpers::{FromExpr, ShortHex};
 use crate::rendering::{IntoValue, Value};
 
 pub mod servicemodelapi {
@@ -66,6 +67,12 @@ impl IntoValue for BlockId {
     }
 }
 
+impl FromExpr for BlockId {
+    fn from(val: rhoapi::expr::ExprInstance) -> anyhow::Result<Self> {
+        FromExpr::from(val).map(Self)
+    }
+}
+
 #[derive(
     Debug,
     Clone,
@@ -89,6 +96,12 @@ impl IntoValue for DeployId {
     }
 }
 
+impl FromExpr for DeployId {
+    fn from(val: rhoapi::expr::ExprInstance) -> anyhow::Result<Self> {
+        FromExpr::from(val).map(Self)
+    }
+}
+
 #[derive(derive_more::Debug, Clone)]
 pub struct SignedCode {
     #[debug("{:?}", contract.short_hex(32))]
@@ -100,6 +113,41 @@ pub struct SignedCode {
     pub deployer: Vec<u8>,
 }
 
+impl SignedCode {
+    /// Checks that contract is exactly `prepared` and is validly signed by its deployer,
+    /// which, if given, must own `expected_deployer` wallet.
+    pub fn verify(
+        &self,
+        prepared: &[u8],
+        expected_deployer: Option<&WalletAddress>,
+    ) -> Result<(), SignedContractError> {
+        if self.contract != prepared {
+            return Err(SignedContractError::ContractMismatch);
+        }
+
+        if self.sig_algorithm != firefly_signing::SIG_ALGORITHM {
+            return Err(SignedContractError::UnsupportedSigAlgorithm(
+                self.sig_algorithm.clone(),
+            ));
+        }
+
+        let deployer = PublicKey::from_slice(&self.deployer)?;
+        if !firefly_signing::verify_contract(&deployer, &self.contract, &self.sig) {
+            return Err(SignedContractError::InvalidSignature);
+        }
+
+        match expected_deployer {
+            Some(expected) if *expected != WalletAddress::from(deployer) => {
+                Err(SignedContractError::DeployerMismatch {
+                    expected: expected.clone(),
+                    actual: deployer.into(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub enum ReadNodeExprUnforg {
     UnforgPrivate { data: String },
@@ -243,6 +291,12 @@ pub struct DeployData {
     #[builder(default = 5_000_000)]
     pub phlo_limit: u64,
 
+    #[builder(default = 1)]
+    pub phlo_price: i64,
+
+    #[builder(default = "root".to_owned(), into)]
+    pub shard_id: String,
+
     #[builder(default = chrono::Utc::now())]
     pub timestamp: DateTime<Utc>,
 
@@ -250,6 +304,116 @@ pub struct DeployData {
     pub valid_after_block_number: ValidAfter,
 }
 
+/// Name created by `new`, identified by its raw bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum UnforgeableName {
+    Private(Vec<u8>),
+    DeployId(Vec<u8>),
+    DeployerId(Vec<u8>),
+}
+
+impl FromExpr for UnforgeableName {
+    fn from(val: rhoapi::expr::ExprInstance) -> anyhow::Result<Self> {
+        Err(anyhow::anyhow!(
+            "unexpected expr type: {val:?} expected unforgeable name"
+        ))
+    }
+
+    fn from_par(par: rhoapi::Par) -> anyhow::Result<Self> {
+        use rhoapi::g_unforgeable::UnfInstance;
+
+        let unforgeable = par
+            .unforgeables
+            .into_iter()
+            .next()
+            .and_then(|unforgeable| unforgeable.unf_instance);
+
+        match unforgeable {
+            Some(UnfInstance::GPrivateBody(body)) => Ok(Self::Private(body.id)),
+            Some(UnfInstance::GDeployIdBody(body)) => Ok(Self::DeployId(body.sig)),
+            Some(UnfInstance::GDeployerIdBody(body)) => Ok(Self::DeployerId(body.public_key)),
+            other => Err(anyhow::anyhow!("unsupported unforgeable: {other:?}")),
+        }
+    }
+}
+
+impl From<UnforgeableName> for rhoapi::Par {
+    fn from(name: UnforgeableName) -> Self {
+        use rhoapi::g_unforgeable::UnfInstance;
+
+        let unf_instance = match name {
+            UnforgeableName::Private(id) => UnfInstance::GPrivateBody(rhoapi::GPrivate { id }),
+            UnforgeableName::DeployId(sig) => UnfInstance::GDeployIdBody(rhoapi::GDeployId { sig }),
+            UnforgeableName::DeployerId(public_key) => {
+                UnfInstance::GDeployerIdBody(rhoapi::GDeployerId { public_key })
+            }
+        };
+
+        Self {
+            unforgeables: vec![rhoapi::GUnforgeable {
+                unf_instance: Some(unf_instance),
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+/// `deployId` name of the deploy, the one bound by `rho:rchain:deployId`.
+impl TryFrom<&DeployId> for UnforgeableName {
+    type Error = hex::FromHexError;
+
+    fn try_from(deploy_id: &DeployId) -> Result<Self, Self::Error> {
+        hex::decode(&deploy_id.0).map(Self::DeployId)
+    }
+}
+
+impl From<String> for rhoapi::Par {
+    fn from(name: String) -> Self {
+        Self {
+            exprs: vec![rhoapi::Expr {
+                expr_instance: Some(rhoapi::expr::ExprInstance::GString(name)),
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&str> for rhoapi::Par {
+    fn from(name: &str) -> Self {
+        name.to_owned().into()
+    }
+}
+
+impl rhoapi::Par {
+    /// Tuple of processes, like `@(deployId, "result")` name.
+    pub fn tuple(elements: impl IntoIterator<Item = Self>) -> Self {
+        Self {
+            exprs: vec![rhoapi::Expr {
+                expr_instance: Some(rhoapi::expr::ExprInstance::ETupleBody(rhoapi::ETuple {
+                    ps: elements.into_iter().collect(),
+                    ..Default::default()
+                })),
+            }],
+            ..Default::default()
+        }
+    }
+}
+
+/// Value found at a channel in the post-state of a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelData<T> {
+    pub block: BlockId,
+    pub block_number: u64,
+    pub value: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeployStatus {
+    Pending,
+    InBlock(BlockId),
+    Finalized { cost: u64, errored: bool },
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "event", rename_all = "kebab-case")]
 pub enum NodeEvent {
@@ -259,10 +423,44 @@ pub enum NodeEvent {
     BlockFinalised { payload: BlockEventPayload },
 }
 
+impl NodeEvent {
+    pub const fn kind(&self) -> NodeEventKind {
+        match self {
+            Self::Started => NodeEventKind::Started,
+            Self::BlockAdded { .. } => NodeEventKind::BlockAdded,
+            Self::BlockCreated { .. } => NodeEventKind::BlockCreated,
+            Self::BlockFinalised { .. } => NodeEventKind::BlockFinalised,
+        }
+    }
+
+    pub const fn payload(&self) -> Option<&BlockEventPayload> {
+        match self {
+            Self::Started => None,
+            Self::BlockAdded { payload }
+            | Self::BlockCreated { payload }
+            | Self::BlockFinalised { payload } => Some(payload),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeEventKind {
+    Started,
+    BlockAdded,
+    BlockCreated,
+    BlockFinalised,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct BlockEventPayload {
     pub block_hash: BlockId,
+    #[serde(default)]
+    pub parent_hashes: Vec<BlockId>,
+    #[serde(default)]
+    pub creator: String,
+    #[serde(default)]
+    pub seq_num: u64,
     pub deploys: Vec<BlockEventDeploy>,
 }
 
@@ -274,10 +472,9 @@ pub struct BlockEventDeploy {
     pub errored: bool,
 }
 
-pub const FIRECAP_ID: [u8; 3] = [0, 0, 0];
-pub const FIRECAP_VERSION: u8 = 0;
+pub use firefly_signing::{FIRECAP_ID, FIRECAP_VERSION};
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Into, AsRef)]
+#[derive(Debug, Clone, Display, PartialEq, Eq, Hash, Serialize, Into, AsRef)]
 pub struct WalletAddress(String);
 
 impl IntoValue for WalletAddress {
@@ -324,60 +521,27 @@ impl TryFrom<String> for WalletAddress {
 
 impl From<PublicKey> for WalletAddress {
     fn from(key: PublicKey) -> Self {
-        let key_hash: [u8; 32] = sha3::Keccak256::new()
-            .chain_update(&key.serialize_uncompressed()[1..])
-            .finalize()
-            .into();
-
-        let eth_hash = sha3::Keccak256::new()
-            .chain_update(&key_hash[key_hash.len() - 20..])
-            .finalize();
-
-        let checksum_hash: [u8; 32] = Blake2b::<U32>::new()
-            .chain_update(FIRECAP_ID)
-            .chain_update([FIRECAP_VERSION])
-            .chain_update(eth_hash)
-            .finalize()
-            .into();
-
-        let checksum = &checksum_hash[0..4];
-
-        let address_bytes = [
-            FIRECAP_ID.as_ref(),
-            [FIRECAP_VERSION].as_ref(),
-            eth_hash.as_ref(),
-            checksum,
-        ]
-        .concat();
-
-        Self(bs58::encode(address_bytes).into_string())
+        Self(firefly_signing::wallet_address(&key))
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Into, AsRef)]
 pub struct Uri(String);
 
-const CRC14: crc::Algorithm<u16> = crc::Algorithm {
-    width: 14,
-    poly: 0x4805,
-    init: 0x0000,
-    refin: false,
-    refout: false,
-    xorout: 0x0000,
-    check: 0,
-    residue: 0x0000,
-};
+impl FromExpr for Uri {
+    fn from(val: rhoapi::expr::ExprInstance) -> anyhow::Result<Self> {
+        match val {
+            rhoapi::expr::ExprInstance::GUri(value) => value.try_into().map_err(Into::into),
+            other => Err(anyhow::anyhow!(
+                "unexpected expr type: {other:?} expected GUri"
+            )),
+        }
+    }
+}
 
 impl From<PublicKey> for Uri {
     fn from(value: PublicKey) -> Self {
-        let hash = Blake2b::<U32>::new()
-            .chain_update(value.serialize_uncompressed())
-            .finalize();
-
-        let crc = Crc::<u16>::new(&CRC14).checksum(&hash).to_ne_bytes();
-        let full_key = [hash.as_ref(), [crc[0], crc[1] << 2].as_ref()].concat();
-        let encoded = zbase32::encode(&full_key, 270);
-        Self(format!("rho:id:{encoded}"))
+        Self(firefly_signing::uri(&value))
     }
 }
 
@@ -412,7 +576,7 @@ impl TryFrom<String> for Uri {
 
         let (hash, crc_bytes) = bytes.split_at(HASH_SIZE);
         let crc = u16::from_ne_bytes([crc_bytes[0], crc_bytes[1] >> 2]);
-        let expected = Crc::<u16>::new(&CRC14).checksum(hash);
+        let expected = Crc::<u16>::new(&firefly_signing::URI_CRC).checksum(hash);
 
         if expected != crc {
             return Err(Self::Error::ChecksumMistmatch);
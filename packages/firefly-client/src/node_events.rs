@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::task::ready;
 use std::time::Duration;
@@ -5,13 +7,26 @@ use std::time::Duration;
 use backon::{ExponentialBuilder, Retryable};
 use dashmap::DashMap;
 use futures::{Stream, StreamExt};
-use tokio::sync::{Notify, broadcast};
+use secp256k1::PublicKey;
+use tokio::sync::{broadcast, mpsc, oneshot};
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_tungstenite::tungstenite::Message;
 use tracing::Instrument;
 use uuid::Uuid;
 
-use crate::models::{BlockEventDeploy, DeployId, NodeEvent, WalletAddress};
+use crate::WriteNodeClient;
+use crate::errors::WriteNodeError;
+use crate::models::casper::BlockInfo;
+use crate::models::{
+    BlockEventDeploy,
+    BlockEventPayload,
+    BlockId,
+    DeployId,
+    NodeEvent,
+    NodeEventKind,
+    WalletAddress,
+};
+use crate::write_node_client::MAX_QUERY_DEPTH;
 
 #[derive(Debug, Clone)]
 pub enum DeployEvent {
@@ -22,19 +37,48 @@ pub enum DeployEvent {
     },
 }
 
-type DeploySubscriptions = Arc<DashMap<DeployId, DashMap<Uuid, Arc<Notify>>>>;
+/// Result of waiting for deploy with [`NodeEvents::wait_for_deploy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeployOutcome {
+    Finalized {
+        block_hash: BlockId,
+        cost: u64,
+        errored: bool,
+    },
+    TimedOut,
+}
+
+type DeploySubscriptions = Arc<DashMap<DeployId, DashMap<Uuid, oneshot::Sender<DeployOutcome>>>>;
 type WalletSubscriptions = Arc<DashMap<WalletAddress, broadcast::Sender<DeployEvent>>>;
 
 #[derive(Clone)]
 pub struct NodeEvents {
+    blocks_tx: broadcast::Sender<NodeEvent>,
     deploy_subscriptions: DeploySubscriptions,
     wallet_subscriptions: WalletSubscriptions,
 }
 
+#[derive(Debug, Clone)]
+enum Signal {
+    Connected,
+    Event(NodeEvent),
+}
+
 impl NodeEvents {
     pub fn new(url: &str) -> Self {
+        Self::start(url, None)
+    }
+
+    /// Same as [`Self::new`], but blocks finalized while websocket was disconnected,
+    /// or while events were lagging behind, are replayed using `client`.
+    pub fn with_backfill(url: &str, client: WriteNodeClient) -> Self {
+        Self::start(url, Some(client))
+    }
+
+    fn start(url: &str, backfill_client: Option<WriteNodeClient>) -> Self {
         let url = format!("{url}/ws/events");
-        let tx = broadcast::Sender::<NodeEvent>::new(32);
+        let tx = broadcast::Sender::<Signal>::new(32);
+        let blocks_tx = broadcast::Sender::<NodeEvent>::new(32);
         let deploy_subscriptions = DeploySubscriptions::default();
         let wallet_subscriptions = WalletSubscriptions::default();
 
@@ -50,6 +94,8 @@ impl NodeEvents {
                         return;
                     };
 
+                    let _ = tx.send(Signal::Connected);
+
                     while let Some(msg) = stream.next().await {
                         let buff = match msg {
                             Ok(Message::Text(buff)) => buff,
@@ -71,40 +117,87 @@ impl NodeEvents {
                             }
                         };
 
-                        let _ = tx.send(event);
+                        let _ = tx.send(Signal::Event(event));
                     }
                 }
             }
             .in_current_span()
         });
 
+        let (caught_up_tx, mut caught_up_rx) = mpsc::unbounded_channel();
+        let mut backfill = backfill_client.map(|client| Backfill::new(client, caught_up_tx));
+
         tokio::spawn({
             let mut rx = tx.subscribe();
+            let blocks_tx = blocks_tx.clone();
             let deploy_subscriptions = deploy_subscriptions.clone();
             let wallet_subscriptions = wallet_subscriptions.clone();
             async move {
                 loop {
-                    let deploys = match rx.recv().await {
-                        Ok(NodeEvent::Started) => continue,
-                        Ok(NodeEvent::BlockAdded { .. }) => continue,
-                        Ok(NodeEvent::BlockCreated { .. }) => continue,
-                        Ok(NodeEvent::BlockFinalised { payload }) => payload.deploys,
-                        Err(broadcast::error::RecvError::Closed) => return,
-                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    let (blocks, live) = tokio::select! {
+                        signal = rx.recv() => match signal {
+                            Ok(Signal::Event(NodeEvent::BlockFinalised { payload })) => {
+                                (vec![payload], true)
+                            }
+                            Ok(Signal::Event(event)) => {
+                                let _ = blocks_tx.send(event);
+                                continue;
+                            }
+                            Ok(Signal::Connected) => {
+                                if let Some(backfill) = &mut backfill {
+                                    backfill.start();
+                                }
+                                continue;
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return,
+                            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                tracing::warn!("node events lagged, skipped {skipped} events");
+                                if let Some(backfill) = &mut backfill {
+                                    backfill.start();
+                                }
+                                continue;
+                            }
+                        },
+                        Some(caught_up) = caught_up_rx.recv() => match &mut backfill {
+                            Some(backfill) => (backfill.finish(caught_up), false),
+                            None => continue,
+                        },
                     };
 
-                    for deploy in deploys {
-                        deploy_subscriptions
-                            .remove(&deploy.id)
-                            .map(|(_, waiters)| waiters)
-                            .into_iter()
-                            .flatten()
-                            .for_each(|(_, w)| w.notify_waiters());
-
-                        if let Some(subscription) =
-                            wallet_subscriptions.get(&deploy.deployer.into())
+                    for block in blocks {
+                        if backfill
+                            .as_mut()
+                            .is_some_and(|backfill| !backfill.mark_seen(&block.block_hash, live))
                         {
-                            let _ = subscription.send(deploy.into());
+                            continue;
+                        }
+
+                        if blocks_tx.receiver_count() > 0 {
+                            let _ = blocks_tx.send(NodeEvent::BlockFinalised {
+                                payload: block.clone(),
+                            });
+                        }
+
+                        for deploy in block.deploys {
+                            let outcome = DeployOutcome::Finalized {
+                                block_hash: block.block_hash.clone(),
+                                cost: deploy.cost,
+                                errored: deploy.errored,
+                            };
+                            deploy_subscriptions
+                                .remove(&deploy.id)
+                                .map(|(_, waiters)| waiters)
+                                .into_iter()
+                                .flatten()
+                                .for_each(|(_, w)| {
+                                    let _ = w.send(outcome.clone());
+                                });
+
+                            if let Some(subscription) =
+                                wallet_subscriptions.get(&deploy.deployer.into())
+                            {
+                                let _ = subscription.send(deploy.into());
+                            }
                         }
                     }
                 }
@@ -113,25 +206,26 @@ impl NodeEvents {
         });
 
         Self {
+            blocks_tx,
             deploy_subscriptions,
             wallet_subscriptions,
         }
     }
 
+    /// Resolves once deploy lands in a finalized block or after `max_wait`.
     pub fn wait_for_deploy(
         &self,
         deploy_id: &DeployId,
         max_wait: Duration,
-    ) -> impl Future<Output = bool> {
+    ) -> impl Future<Output = DeployOutcome> {
         let id = Uuid::now_v7();
 
-        let notify = Arc::<Notify>::default();
-        let notified = notify.clone().notified_owned();
+        let (tx, rx) = oneshot::channel();
 
         self.deploy_subscriptions
             .entry(deploy_id.clone())
             .or_default()
-            .insert(id, notify);
+            .insert(id, tx);
 
         let guard = scopeguard::guard(
             self.deploy_subscriptions.clone(),
@@ -145,15 +239,24 @@ impl NodeEvents {
 
         async move {
             tokio::select! {
-                _ = notified => {
+                Ok(outcome) = rx => {
                     scopeguard::ScopeGuard::into_inner(guard); // defuse
-                    true
+                    outcome
                 },
-                _ = tokio::time::sleep(max_wait) => false,
+                _ = tokio::time::sleep(max_wait) => DeployOutcome::TimedOut,
             }
         }
     }
 
+    /// Streams node events matching `filter`, sharing the websocket with other subscribers.
+    /// Finalized blocks replayed by backfill are included, lagging subscriber skips events.
+    pub fn subscribe_blocks(&self, filter: BlockFilter) -> BlockSubscription {
+        BlockSubscription {
+            filter,
+            rx: BroadcastStream::new(self.blocks_tx.subscribe()),
+        }
+    }
+
     pub fn subscribe_for_deploys(&self, wallet_address: WalletAddress) -> WalletSubscription {
         let tx = self
             .wallet_subscriptions
@@ -178,6 +281,57 @@ impl From<BlockEventDeploy> for DeployEvent {
     }
 }
 
+/// Selects events for [`NodeEvents::subscribe_blocks`], empty `kinds` selects all kinds.
+#[derive(Debug, Clone, Default, bon::Builder)]
+pub struct BlockFilter {
+    #[builder(default, into)]
+    pub kinds: Vec<NodeEventKind>,
+    /// Keeps only block events with at least one deploy from this deployer.
+    pub deployer: Option<PublicKey>,
+}
+
+impl BlockFilter {
+    pub fn matches(&self, event: &NodeEvent) -> bool {
+        if !self.kinds.is_empty() && !self.kinds.contains(&event.kind()) {
+            return false;
+        }
+
+        self.deployer.is_none_or(|deployer| {
+            event.payload().is_some_and(|payload| {
+                payload
+                    .deploys
+                    .iter()
+                    .any(|deploy| deploy.deployer == deployer)
+            })
+        })
+    }
+}
+
+pub struct BlockSubscription {
+    filter: BlockFilter,
+    rx: BroadcastStream<NodeEvent>,
+}
+
+impl Stream for BlockSubscription {
+    type Item = NodeEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        loop {
+            match ready!(self.rx.poll_next_unpin(cx)) {
+                Some(Ok(event)) if self.filter.matches(&event) => {
+                    return std::task::Poll::Ready(Some(event));
+                }
+                Some(Ok(_)) => {}
+                Some(Err(err)) => tracing::debug!("block subscription: {err}"),
+                None => return std::task::Poll::Ready(None),
+            }
+        }
+    }
+}
+
 pub struct WalletSubscription {
     wallet_address: WalletAddress,
     wallet_subscriptions: WalletSubscriptions,
@@ -205,3 +359,191 @@ impl Drop for WalletSubscription {
             });
     }
 }
+
+/// Tracks finalized blocks already dispatched and recovers the ones missed in between.
+///
+/// Missed blocks are fetched by a separate task, so live events keep flowing meanwhile.
+/// The cursor stays put until the fetch succeeds, failed fetches are retried.
+struct Backfill {
+    client: WriteNodeClient,
+    /// Every finalized block up to this one was dispatched.
+    cursor: Option<BlockId>,
+    seen: VecDeque<BlockId>,
+    state: BackfillState,
+    caught_up_tx: mpsc::UnboundedSender<CaughtUp>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BackfillState {
+    Idle,
+    Running,
+    /// Another gap appeared while running, so it has to run once more.
+    Rerun,
+}
+
+/// Blocks missed since the cursor along with the new cursor.
+struct CaughtUp {
+    cursor: BlockId,
+    blocks: Vec<BlockEventPayload>,
+}
+
+impl Backfill {
+    /// Enough to dedupe blocks replayed and received from websocket around reconnect.
+    const SEEN_CAPACITY: usize = 1024;
+
+    const fn new(client: WriteNodeClient, caught_up_tx: mpsc::UnboundedSender<CaughtUp>) -> Self {
+        Self {
+            client,
+            cursor: None,
+            seen: VecDeque::new(),
+            state: BackfillState::Idle,
+            caught_up_tx,
+        }
+    }
+
+    /// Returns `false` if block was already dispatched.
+    ///
+    /// Live blocks advance the cursor unless there is a gap behind them still being fetched.
+    fn mark_seen(&mut self, block_hash: &BlockId, live: bool) -> bool {
+        if self.seen.contains(block_hash) {
+            return false;
+        }
+
+        if self.seen.len() == Self::SEEN_CAPACITY {
+            self.seen.pop_front();
+        }
+        self.seen.push_back(block_hash.clone());
+        if live && self.state == BackfillState::Idle {
+            self.cursor = Some(block_hash.clone());
+        }
+        true
+    }
+
+    /// Starts fetching blocks missed since the cursor, result is sent to the dispatcher.
+    fn start(&mut self) {
+        match self.state {
+            BackfillState::Idle => {}
+            BackfillState::Running | BackfillState::Rerun => {
+                self.state = BackfillState::Rerun;
+                return;
+            }
+        }
+        self.state = BackfillState::Running;
+
+        let caught_up_tx = self.caught_up_tx.clone();
+        let client = self.client.clone();
+        let cursor = self.cursor.clone();
+        let seen = self.seen.clone();
+        tokio::spawn(
+            async move {
+                let caught_up = (|| missed_blocks(client.clone(), cursor.as_ref(), &seen))
+                    .retry(ExponentialBuilder::default().without_max_times())
+                    .when(|_| !caught_up_tx.is_closed())
+                    .notify(|err, after| {
+                        tracing::warn!(
+                            "failed to backfill node events, retrying in {after:?}: {err}"
+                        );
+                    })
+                    .await;
+
+                match caught_up {
+                    Ok(caught_up) => {
+                        let _ = caught_up_tx.send(caught_up);
+                    }
+                    Err(err) => tracing::debug!("backfill stopped: {err}"),
+                }
+            }
+            .in_current_span(),
+        );
+    }
+
+    /// Moves the cursor past the fetched blocks and returns the ones to dispatch.
+    fn finish(&mut self, caught_up: CaughtUp) -> Vec<BlockEventPayload> {
+        self.cursor = Some(caught_up.cursor);
+        let rerun = self.state == BackfillState::Rerun;
+        self.state = BackfillState::Idle;
+        if rerun {
+            self.start();
+        }
+        caught_up.blocks
+    }
+}
+
+async fn missed_blocks(
+    mut client: WriteNodeClient,
+    cursor: Option<&BlockId>,
+    seen: &VecDeque<BlockId>,
+) -> Result<CaughtUp, WriteNodeError> {
+    let (last_finalized, end) = block_number(client.last_finalized_block().await?)?;
+
+    let Some(cursor) = cursor else {
+        // nothing was dispatched yet, so nothing could be missed
+        return Ok(CaughtUp {
+            cursor: last_finalized,
+            blocks: vec![],
+        });
+    };
+
+    let (_, start) = block_number(client.get_block(cursor).await?)?;
+    if end <= start {
+        return Ok(CaughtUp {
+            cursor: cursor.clone(),
+            blocks: vec![],
+        });
+    }
+
+    // nodes refuse long height ranges, so long gaps are fetched in pages
+    let depth: u64 = MAX_QUERY_DEPTH.into();
+    let mut heights = vec![];
+    for page_start in (start + 1..=end).step_by(MAX_QUERY_DEPTH as usize) {
+        let page_end = end.min(page_start.saturating_add(depth - 1));
+        heights.extend(client.get_blocks_by_heights(page_start, page_end).await?);
+    }
+
+    let mut blocks = vec![];
+    for block in heights {
+        let block_hash: BlockId = block.block_hash.into();
+        if seen.contains(&block_hash) || !client.is_finalized(&block_hash).await? {
+            continue;
+        }
+
+        let block = client.get_block(&block_hash).await?;
+        let info = block.block_info.unwrap_or_default();
+        blocks.push(BlockEventPayload {
+            block_hash,
+            parent_hashes: info.parents_hash_list.into_iter().map(Into::into).collect(),
+            creator: info.sender,
+            seq_num: info.seq_num as _,
+            deploys: block
+                .deploys
+                .into_iter()
+                .filter_map(|deploy| {
+                    PublicKey::from_str(&deploy.deployer)
+                        .inspect_err(|err| {
+                            tracing::debug!("invalid deployer in {}: {err}", deploy.sig);
+                        })
+                        .ok()
+                        .map(|deployer| BlockEventDeploy {
+                            id: deploy.sig.into(),
+                            cost: deploy.cost,
+                            deployer,
+                            errored: deploy.errored,
+                        })
+                })
+                .collect(),
+        });
+    }
+
+    tracing::debug!("backfilled {} finalized blocks", blocks.len());
+    Ok(CaughtUp {
+        cursor: last_finalized,
+        blocks,
+    })
+}
+
+fn block_number(block: BlockInfo) -> Result<(BlockId, u64), WriteNodeError> {
+    block
+        .block_info
+        .map(|info| (info.block_hash.into(), info.block_number as _))
+        .ok_or_else(|| WriteNodeError::ResponseFormat("missing block_info".into()))
+}
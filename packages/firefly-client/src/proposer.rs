@@ -0,0 +1,212 @@
+use std::collections::{BTreeMap, HashSet};
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::MissedTickBehavior;
+
+use crate::WriteNodeClient;
+use crate::errors::{ProposeRefusal, ProposerError, WriteNodeError};
+use crate::models::{BlockId, DeployId};
+
+#[derive(Debug, Clone, bon::Builder)]
+pub struct ProposerConfig {
+    /// How often deploys waiting for a block are proposed.
+    #[builder(default = Duration::from_secs(1))]
+    pub interval: Duration,
+    /// Number of waiting deploys that triggers propose before the interval elapses.
+    #[builder(default = 16)]
+    pub max_batch: usize,
+    /// Number of proposes a deploy may miss before its caller gets an error.
+    #[builder(default = 5)]
+    pub max_attempts: usize,
+}
+
+impl Default for ProposerConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+struct Submission {
+    deploy_id: DeployId,
+    /// Client bound to the validator holding the deploy.
+    client: WriteNodeClient,
+    attempts: usize,
+    tx: oneshot::Sender<Result<BlockId, ProposerError>>,
+}
+
+/// Shared proposer that coalesces proposes for deploys handed to it.
+///
+/// Instead of proposing after every deploy, waiting deploys are proposed together
+/// once per interval or as soon as enough of them pile up. Each caller is notified
+/// with the block its deploy ended up in, whoever proposed it.
+///
+/// Deploys are proposed by the validators that accepted them through
+/// the client the proposer was started with or its clones.
+#[derive(Clone)]
+pub struct Proposer {
+    client: WriteNodeClient,
+    tx: mpsc::UnboundedSender<Submission>,
+}
+
+impl Proposer {
+    /// Spawns proposing task, which stops once every clone of the proposer is dropped.
+    pub fn start(client: WriteNodeClient, config: ProposerConfig) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(run(config, rx));
+        Self { client, tx }
+    }
+
+    /// Resolves to the block that included already sent deploy.
+    pub fn propose(
+        &self,
+        deploy_id: DeployId,
+    ) -> impl Future<Output = Result<BlockId, ProposerError>> + use<> {
+        let (tx, rx) = oneshot::channel();
+        let sent = self.tx.send(Submission {
+            client: self.client.for_deploy(&deploy_id),
+            deploy_id,
+            attempts: 0,
+            tx,
+        });
+
+        async move {
+            sent.map_err(|_| ProposerError::Stopped)?;
+            rx.await.map_err(|_| ProposerError::Stopped)?
+        }
+    }
+}
+
+async fn run(config: ProposerConfig, mut rx: mpsc::UnboundedReceiver<Submission>) {
+    let mut waiting = Vec::new();
+    let mut interval = tokio::time::interval(config.interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        let stopped = tokio::select! {
+            submission = rx.recv() => {
+                if let Some(submission) = submission {
+                    waiting.push(submission);
+                    if waiting.len() < config.max_batch {
+                        continue;
+                    }
+                    false
+                } else {
+                    interval.tick().await;
+                    true
+                }
+            }
+            _ = interval.tick() => false,
+        };
+
+        while let Ok(submission) = rx.try_recv() {
+            waiting.push(submission);
+        }
+        waiting.retain(|submission: &Submission| !submission.tx.is_closed());
+
+        if !waiting.is_empty() {
+            waiting = propose_batch(waiting, config.max_attempts).await;
+        }
+
+        if stopped && waiting.is_empty() {
+            return;
+        }
+    }
+}
+
+/// Proposes on every validator holding waiting deploys and notifies callers whose deploys
+/// are now in a block, returning submissions that still wait for one.
+async fn propose_batch(waiting: Vec<Submission>, max_attempts: usize) -> Vec<Submission> {
+    let mut by_validator: BTreeMap<String, Vec<Submission>> = BTreeMap::new();
+    for submission in waiting {
+        by_validator
+            .entry(submission.client.validator_url().to_owned())
+            .or_default()
+            .push(submission);
+    }
+
+    futures::future::join_all(
+        by_validator
+            .into_values()
+            .map(|waiting| propose_on_validator(waiting, max_attempts)),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Proposes once on the validator all `waiting` deploys were sent to.
+async fn propose_on_validator(waiting: Vec<Submission>, max_attempts: usize) -> Vec<Submission> {
+    let Some(mut client) = waiting.first().map(|submission| submission.client.clone()) else {
+        return waiting;
+    };
+    let proposed = propose(&mut client).await;
+
+    // deploys of the proposed block are resolved in one go,
+    // the rest could have been proposed by someone else
+    let proposed_block = match &proposed {
+        Ok(Some(block_id)) => client.get_block(block_id).await.ok().map(|block| {
+            let included: HashSet<_> = block.deploys.into_iter().map(|deploy| deploy.sig).collect();
+            (block_id.clone(), included)
+        }),
+        _ => None,
+    };
+
+    let mut still_waiting = Vec::new();
+    for mut submission in waiting {
+        let block = match &proposed_block {
+            Some((block_id, included)) if included.contains(submission.deploy_id.as_ref()) => {
+                Some(block_id.clone())
+            }
+            _ => client
+                .find_deploy(&submission.deploy_id)
+                .await
+                .ok()
+                .flatten()
+                .map(|block| block.block_hash.into()),
+        };
+
+        if let Some(block) = block {
+            let _ = submission.tx.send(Ok(block));
+            continue;
+        }
+
+        submission.attempts += 1;
+        if submission.attempts < max_attempts {
+            still_waiting.push(submission);
+            continue;
+        }
+
+        let reason = match &proposed {
+            Ok(_) => "deploy is not in any block".to_owned(),
+            Err(err) => err.to_string(),
+        };
+        let _ = submission.tx.send(Err(ProposerError::NotIncluded {
+            attempts: submission.attempts,
+            reason,
+        }));
+    }
+
+    still_waiting
+}
+
+/// Proposes asynchronously and waits for the result, `None` if there was nothing to propose.
+async fn propose(client: &mut WriteNodeClient) -> Result<Option<BlockId>, WriteNodeError> {
+    let result = match client.propose_async().await {
+        Ok(()) => client.propose_result().await,
+        Err(err) => Err(err),
+    };
+
+    match result {
+        Ok(block_id) => {
+            tracing::debug!("proposed block {block_id}");
+            Ok(Some(block_id))
+        }
+        Err(WriteNodeError::ProposeRefused(ProposeRefusal::NoNewDeploys)) => Ok(None),
+        Err(err) => {
+            tracing::warn!("propose failed: {err}");
+            Err(err)
+        }
+    }
+}
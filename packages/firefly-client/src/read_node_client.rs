@@ -1,58 +1,160 @@
 use anyhow::Context;
 use serde_json::Value;
 
+use crate::deserializer::from_par;
 use crate::errors::ReadNodeError;
-use crate::models::ReadNodeExpr;
+use crate::helpers::FromExpr;
+use crate::models::casper::v1::deploy_service_client::DeployServiceClient;
+use crate::models::casper::v1::exploratory_deploy_response;
+use crate::models::casper::{DataWithBlockInfo, ExploratoryDeployQuery};
+use crate::models::rhoapi::Par;
+use crate::models::{BlockId, ReadNodeExpr};
+
+#[derive(Clone)]
+enum Transport {
+    Http {
+        url: String,
+        client: reqwest::Client,
+    },
+    Grpc {
+        deploy_client: DeployServiceClient<tonic::transport::Channel>,
+    },
+}
 
 #[derive(Clone)]
 pub struct ReadNodeClient {
-    url: String,
-    client: reqwest::Client,
+    transport: Transport,
 }
 
 impl ReadNodeClient {
     pub fn new(url: String) -> Self {
         Self {
-            url,
-            client: Default::default(),
+            transport: Transport::Http {
+                url,
+                client: Default::default(),
+            },
         }
     }
 
+    /// Reads through the `exploratoryDeploy` RPC instead of the observer's HTTP api.
+    pub async fn new_grpc(deploy_service_url: String) -> anyhow::Result<Self> {
+        let deploy_client = DeployServiceClient::connect(deploy_service_url)
+            .await
+            .context("failed to connect to deploy service")?;
+
+        Ok(Self {
+            transport: Transport::Grpc { deploy_client },
+        })
+    }
+
     pub async fn get_data<T>(&self, rholang_code: String) -> Result<T, ReadNodeError>
     where
         T: serde::de::DeserializeOwned,
     {
-        let mut response_json = self.explore_deploy(rholang_code).await?;
+        match &self.transport {
+            Transport::Http { url, client } => {
+                let mut response_json = explore_deploy_http(client, url, rholang_code).await?;
+
+                let data_value = response_json
+                    .pointer_mut("/expr/0")
+                    .map(Value::take)
+                    .ok_or(ReadNodeError::ReturnValueMissing)?;
+
+                let intermediate: ReadNodeExpr = serde_json::from_value(data_value)
+                    .context("failed to deserialize intermediate model")
+                    .map_err(ReadNodeError::Deserialization)?;
+
+                serde_json::from_value(intermediate.into())
+                    .context("failed to deserialize filed model")
+                    .map_err(ReadNodeError::Deserialization)
+            }
+            Transport::Grpc { deploy_client } => {
+                let par = explore_deploy_grpc(deploy_client.clone(), rholang_code, None)
+                    .await
+                    .and_then(first_value)?;
+
+                from_par(par)
+                    .context("failed to deserialize filed model")
+                    .map_err(ReadNodeError::Deserialization)
+            }
+        }
+    }
+
+    /// Runs exploratory deploy over gRPC, optionally pinned to `block_hash`,
+    /// and decodes the returned value with [`FromExpr`].
+    pub async fn get_data_at<T>(
+        &self,
+        rholang_code: String,
+        block_hash: Option<BlockId>,
+    ) -> Result<T, ReadNodeError>
+    where
+        T: FromExpr,
+    {
+        let Transport::Grpc { deploy_client } = &self.transport else {
+            return Err(ReadNodeError::GrpcTransportRequired);
+        };
 
-        let data_value = response_json
-            .pointer_mut("/expr/0")
-            .map(Value::take)
+        let par = explore_deploy_grpc(deploy_client.clone(), rholang_code, block_hash)
+            .await
+            .and_then(first_value)?;
+
+        let expr = par
+            .exprs
+            .into_iter()
+            .next()
+            .and_then(|expr| expr.expr_instance)
             .ok_or(ReadNodeError::ReturnValueMissing)?;
 
-        let intermediate: ReadNodeExpr = serde_json::from_value(data_value)
-            .context("failed to deserialize intermediate model")
-            .map_err(ReadNodeError::Deserialization)?;
+        T::from(expr).map_err(ReadNodeError::Deserialization)
+    }
+}
+
+async fn explore_deploy_http(
+    client: &reqwest::Client,
+    url: &str,
+    rholang_code: String,
+) -> Result<Value, ReadNodeError> {
+    let request = client
+        .post(format!("{url}/api/explore-deploy"))
+        .body(rholang_code)
+        .header("Content-Type", "text/plain")
+        .send()
+        .await?;
 
-        serde_json::from_value(intermediate.into())
-            .context("failed to deserialize filed model")
-            .map_err(ReadNodeError::Deserialization)
+    if !request.status().is_success() {
+        let status = request.status();
+        let body = request.text().await?;
+        return Err(ReadNodeError::Api(status, body));
     }
 
-    async fn explore_deploy(&self, rholang_code: String) -> Result<Value, ReadNodeError> {
-        let request = self
-            .client
-            .post(format!("{}/api/explore-deploy", self.url))
-            .body(rholang_code)
-            .header("Content-Type", "text/plain")
-            .send()
-            .await?;
-
-        if !request.status().is_success() {
-            let status = request.status();
-            let body = request.text().await?;
-            return Err(ReadNodeError::Api(status, body));
-        }
+    request.json().await.map_err(Into::into)
+}
 
-        request.json().await.map_err(Into::into)
+async fn explore_deploy_grpc(
+    mut deploy_client: DeployServiceClient<tonic::transport::Channel>,
+    rholang_code: String,
+    block_hash: Option<BlockId>,
+) -> Result<DataWithBlockInfo, ReadNodeError> {
+    let resp = deploy_client
+        .exploratory_deploy(ExploratoryDeployQuery {
+            term: rholang_code,
+            block_hash: block_hash.map(Into::into).unwrap_or_default(),
+            use_pre_state_hash: false,
+        })
+        .await?
+        .into_inner()
+        .message
+        .ok_or(ReadNodeError::ReturnValueMissing)?;
+
+    match resp {
+        exploratory_deploy_response::Message::Result(data) => Ok(data),
+        exploratory_deploy_response::Message::Error(err) => Err(ReadNodeError::Service(err.into())),
     }
 }
+
+fn first_value(data: DataWithBlockInfo) -> Result<Par, ReadNodeError> {
+    data.post_block_data
+        .into_iter()
+        .next()
+        .ok_or(ReadNodeError::ReturnValueMissing)
+}
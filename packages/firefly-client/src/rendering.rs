@@ -1,5 +1,8 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fmt;
+use std::hash::BuildHasher;
+use std::num::TryFromIntError;
 
 use derive_more::{From, Into};
 pub use firefly_client_macros::{IntoValue, Render};
@@ -7,6 +10,10 @@ use uuid::Uuid;
 
 use crate::models::{DeployData, DeployDataBuilder};
 
+mod parser;
+
+pub use parser::{MAX_PARSE_DEPTH, ParseValueError};
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Value {
     Tuple(Vec<Self>),
@@ -27,6 +34,13 @@ pub trait IntoValue {
     fn into_value(self) -> Value;
 }
 
+/// Values rendered as a Rholang map, only these can be `#[into_value(flatten)]`-ed into the enclosing map.
+///
+/// Derived for structs with named fields and for internally or adjacently tagged enums.
+pub trait IntoMap {
+    fn into_map(self) -> BTreeMap<String, Value>;
+}
+
 impl IntoValue for Value {
     fn into_value(self) -> Value {
         self
@@ -98,6 +112,27 @@ impl IntoValue for i64 {
     }
 }
 
+impl IntoValue for u16 {
+    fn into_value(self) -> Value {
+        Value::Int(self.into())
+    }
+}
+
+impl IntoValue for u32 {
+    fn into_value(self) -> Value {
+        Value::Int(self.into())
+    }
+}
+
+/// Checked since Rholang ints are `i64`, which is also why `u64` has no [`IntoValue`].
+impl TryFrom<u64> for Value {
+    type Error = TryFromIntError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        i64::try_from(value).map(Self::Int)
+    }
+}
+
 impl IntoValue for String {
     fn into_value(self) -> Value {
         Value::String(self)
@@ -128,6 +163,18 @@ impl<T: IntoValue> IntoValue for Vec<T> {
     }
 }
 
+impl<const N: usize> IntoValue for [u8; N] {
+    fn into_value(self) -> Value {
+        Value::Bytes(self.to_vec())
+    }
+}
+
+impl<T: IntoValue, const N: usize> IntoValue for [T; N] {
+    fn into_value(self) -> Value {
+        Value::List(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
 impl<T: IntoValue> IntoValue for BTreeSet<T> {
     fn into_value(self) -> Value {
         Value::Set(self.into_iter().map(IntoValue::into_value).collect())
@@ -150,12 +197,91 @@ impl<T: IntoValue> IntoValue for BTreeMap<&str, T> {
     }
 }
 
+impl<T: IntoValue> IntoMap for BTreeMap<String, T> {
+    fn into_map(self) -> BTreeMap<String, Value> {
+        self.into_iter().map(|(k, v)| (k, v.into_value())).collect()
+    }
+}
+
+impl<T: IntoValue> IntoMap for BTreeMap<&str, T> {
+    fn into_map(self) -> BTreeMap<String, Value> {
+        self.into_iter()
+            .map(|(k, v)| (k.to_owned(), v.into_value()))
+            .collect()
+    }
+}
+
+impl<T: IntoValue, S: BuildHasher> IntoValue for HashSet<T, S> {
+    fn into_value(self) -> Value {
+        Value::Set(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+impl<T: IntoValue, S: BuildHasher> IntoValue for HashMap<String, T, S> {
+    fn into_value(self) -> Value {
+        Value::Map(self.into_iter().map(|(k, v)| (k, v.into_value())).collect())
+    }
+}
+
+impl<T: IntoValue, S: BuildHasher> IntoValue for HashMap<&str, T, S> {
+    fn into_value(self) -> Value {
+        Value::Map(
+            self.into_iter()
+                .map(|(k, v)| (k.to_owned(), v.into_value()))
+                .collect(),
+        )
+    }
+}
+
+impl<T: IntoValue, S: BuildHasher> IntoMap for HashMap<String, T, S> {
+    fn into_map(self) -> BTreeMap<String, Value> {
+        self.into_iter().map(|(k, v)| (k, v.into_value())).collect()
+    }
+}
+
+impl<T: IntoValue, S: BuildHasher> IntoMap for HashMap<&str, T, S> {
+    fn into_map(self) -> BTreeMap<String, Value> {
+        self.into_iter()
+            .map(|(k, v)| (k.to_owned(), v.into_value()))
+            .collect()
+    }
+}
+
+impl<T: IntoValue> IntoValue for Box<T> {
+    fn into_value(self) -> Value {
+        (*self).into_value()
+    }
+}
+
+impl<T: IntoMap> IntoMap for Box<T> {
+    fn into_map(self) -> BTreeMap<String, Value> {
+        (*self).into_map()
+    }
+}
+
+impl<B> IntoValue for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: IntoValue,
+{
+    fn into_value(self) -> Value {
+        self.into_owned().into_value()
+    }
+}
+
 impl<T: IntoValue> IntoValue for Option<T> {
     fn into_value(self) -> Value {
         self.map_or(Value::Nil, IntoValue::into_value)
     }
 }
 
+/// Missing value flattens into no entries.
+impl<T: IntoMap> IntoMap for Option<T> {
+    fn into_map(self) -> BTreeMap<String, Value> {
+        self.map(IntoMap::into_map).unwrap_or_default()
+    }
+}
+
 impl IntoValue for Uuid {
     fn into_value(self) -> Value {
         self.to_string().into_value()
@@ -178,7 +304,18 @@ impl IntoValue for Inline {
 }
 
 fn escape_rho_string(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 fn display_iterable<T, F>(values: T, f: &mut fmt::Formatter<'_>, mut format: F) -> fmt::Result
@@ -210,6 +347,9 @@ impl fmt::Display for Value {
             Self::Tuple(values) => {
                 f.write_str("(")?;
                 display_iterable(values, f, |f, entry| entry.fmt(f))?;
+                if values.len() == 1 {
+                    f.write_str(",")?;
+                }
                 f.write_str(")")
             }
             Self::List(values) => {
@@ -0,0 +1,272 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::rendering::Value;
+
+/// Deepest nesting of tuples, lists, sets and maps accepted, input is user supplied
+/// and parsing recurses once per level.
+pub const MAX_PARSE_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{message} at position {position}")]
+pub struct ParseValueError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl FromStr for Value {
+    type Err = ParseValueError;
+
+    /// Parses data literal subset of Rholang that [`Value`] is rendered to.
+    /// [`Value::Inline`] is never produced since it holds arbitrary code.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            input: s,
+            position: 0,
+        };
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+
+        if parser.position != s.len() {
+            return Err(parser.error("unexpected trailing input"));
+        }
+
+        Ok(value)
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: impl Into<String>) -> ParseValueError {
+        ParseValueError {
+            position: self.position,
+            message: message.into(),
+        }
+    }
+
+    fn rest(&self) -> &str {
+        &self.input[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseValueError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(format!("expected `{token}`")))
+        }
+    }
+
+    /// Keywords must not be followed by identifier characters, so `Nilx` is rejected.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        self.skip_whitespace();
+        let matches = self.rest().strip_prefix(keyword).is_some_and(|rest| {
+            !rest
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_')
+        });
+
+        if matches {
+            self.position += keyword.len();
+        }
+        matches
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Value, ParseValueError> {
+        self.skip_whitespace();
+
+        if depth > MAX_PARSE_DEPTH {
+            return Err(self.error(format!("nesting deeper than {MAX_PARSE_DEPTH}")));
+        }
+
+        if self.eat_keyword("Nil") {
+            return Ok(Value::Nil);
+        }
+        if self.eat_keyword("true") {
+            return Ok(Value::Bool(true));
+        }
+        if self.eat_keyword("false") {
+            return Ok(Value::Bool(false));
+        }
+        if self.rest().starts_with("Set(") {
+            self.position += "Set".len();
+            return self.set(depth);
+        }
+
+        match self.peek() {
+            Some('"') => self.string_or_bytes(),
+            Some('`') => self.uri(),
+            Some('(') => self.tuple(depth),
+            Some('[') => self.list(depth),
+            Some('{') => self.map(depth),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.int(),
+            Some(c) => Err(self.error(format!("unexpected character `{c}`"))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn int(&mut self) -> Result<Value, ParseValueError> {
+        let start = self.position;
+        if self.peek() == Some('-') {
+            self.bump();
+        }
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+
+        self.input[start..self.position]
+            .parse()
+            .map(Value::Int)
+            .map_err(|err| ParseValueError {
+                position: start,
+                message: format!("invalid int: {err}"),
+            })
+    }
+
+    fn string(&mut self) -> Result<String, ParseValueError> {
+        self.expect("\"")?;
+
+        let mut string = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(string),
+                Some('\\') => match self.bump() {
+                    Some('"') => string.push('"'),
+                    Some('\\') => string.push('\\'),
+                    Some('n') => string.push('\n'),
+                    Some('t') => string.push('\t'),
+                    Some('r') => string.push('\r'),
+                    Some(c) => return Err(self.error(format!("unknown escape `\\{c}`"))),
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) => string.push(c),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn string_or_bytes(&mut self) -> Result<Value, ParseValueError> {
+        let start = self.position;
+        let string = self.string()?;
+
+        if !self.rest().starts_with(".hexToBytes()") {
+            return Ok(Value::String(string));
+        }
+
+        self.position += ".hexToBytes()".len();
+        hex::decode(&string)
+            .map(Value::Bytes)
+            .map_err(|err| ParseValueError {
+                position: start,
+                message: format!("invalid hex: {err}"),
+            })
+    }
+
+    fn uri(&mut self) -> Result<Value, ParseValueError> {
+        self.expect("`")?;
+
+        let end = self
+            .rest()
+            .find('`')
+            .ok_or_else(|| self.error("unterminated uri"))?;
+        let uri = self.rest()[..end].to_owned();
+        self.position += end + 1;
+
+        Ok(Value::Uri(uri))
+    }
+
+    /// Parses comma separated items until `close`, reporting whether trailing comma was present.
+    fn items<T>(
+        &mut self,
+        close: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T, ParseValueError>,
+    ) -> Result<(Vec<T>, bool), ParseValueError> {
+        let mut items = Vec::new();
+
+        if self.eat(close) {
+            return Ok((items, false));
+        }
+
+        loop {
+            items.push(item(self)?);
+
+            if self.eat(close) {
+                return Ok((items, false));
+            }
+
+            self.expect(",")?;
+
+            if self.eat(close) {
+                return Ok((items, true));
+            }
+        }
+    }
+
+    /// `(x)` is just parenthesized `x`, single element tuple is written as `(x,)`.
+    fn tuple(&mut self, depth: usize) -> Result<Value, ParseValueError> {
+        self.expect("(")?;
+        let (mut values, trailing_comma) = self.items(")", |parser| parser.value(depth + 1))?;
+
+        if values.len() == 1 && !trailing_comma {
+            return Ok(values.remove(0));
+        }
+
+        Ok(Value::Tuple(values))
+    }
+
+    fn list(&mut self, depth: usize) -> Result<Value, ParseValueError> {
+        self.expect("[")?;
+        let (values, _) = self.items("]", |parser| parser.value(depth + 1))?;
+        Ok(Value::List(values))
+    }
+
+    fn set(&mut self, depth: usize) -> Result<Value, ParseValueError> {
+        self.expect("(")?;
+        let (values, _) = self.items(")", |parser| parser.value(depth + 1))?;
+        Ok(Value::Set(values.into_iter().collect::<BTreeSet<_>>()))
+    }
+
+    fn map(&mut self, depth: usize) -> Result<Value, ParseValueError> {
+        self.expect("{")?;
+        let (entries, _) = self.items("}", |parser| {
+            parser.skip_whitespace();
+            let key = parser.string()?;
+            parser.expect(":")?;
+            let value = parser.value(depth + 1)?;
+            Ok((key, value))
+        })?;
+
+        Ok(Value::Map(entries.into_iter().collect::<BTreeMap<_, _>>()))
+    }
+}
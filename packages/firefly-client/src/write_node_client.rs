@@ -1,172 +1,566 @@
-use anyhow::{Context, anyhow};
-use blake2::digest::consts::U32;
-use blake2::{Blake2b, Digest};
-use futures::TryStreamExt;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use futures::{Stream, TryStreamExt, stream};
 use prost::Message as _;
-use secp256k1::{Message, Secp256k1, SecretKey};
+use secp256k1::SecretKey;
+use tokio::task::JoinHandle;
+use tonic::transport::{Channel, Endpoint};
 
+use crate::errors::{ProposeRefusal, ServiceError, WriteNodeError};
 use crate::helpers::FromExpr;
 use crate::models::casper::v1::deploy_service_client::DeployServiceClient;
 use crate::models::casper::v1::propose_service_client::ProposeServiceClient;
 use crate::models::casper::v1::{
+    BlockInfoResponse,
     block_info_response,
+    block_response,
     deploy_response,
+    event_info_response,
+    find_deploy_response,
+    is_finalized_response,
+    last_finalized_block_response,
+    listening_name_data_response,
+    private_name_preview_response,
     propose_response,
+    propose_result_response,
     rho_data_response,
+    status_response,
+    visualize_blocks_response,
+};
+use crate::models::casper::{
+    BlockEventInfo,
+    BlockInfo,
+    BlockQuery,
+    BlocksQuery,
+    BlocksQueryByHeight,
+    DataAtNameByBlockQuery,
+    DataAtNameQuery,
+    DataWithBlockInfo,
+    DeployDataProto,
+    FindDeployQuery,
+    IsFinalizedQuery,
+    LastFinalizedBlockQuery,
+    LightBlockInfo,
+    PrivateNamePreviewQuery,
+    ProposeQuery,
+    ProposeResultQuery,
+    ReportQuery,
+    VisualizeDagQuery,
+};
+use crate::models::rhoapi::Par;
+use crate::models::{
+    BlockId,
+    ChannelData,
+    DeployData,
+    DeployId,
+    DeployStatus,
+    SignedCode,
+    UnforgeableName,
+    ValidAfter,
 };
-use crate::models::casper::{BlocksQuery, DataAtNameByBlockQuery, DeployDataProto, ProposeQuery};
-use crate::models::rhoapi::expr::ExprInstance;
-use crate::models::rhoapi::{Expr, Par};
-use crate::models::{BlockId, DeployData, DeployId, SignedCode, ValidAfter};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const DEPLOY_RETRIES: usize = 3;
+const DEPLOY_RETRY_MIN_DELAY: Duration = Duration::from_millis(200);
+/// Number of blocks or heights asked for at once, nodes refuse deeper queries and longer ranges.
+pub const MAX_QUERY_DEPTH: u32 = 50;
+
+/// Validator serving deploy and propose service calls.
+struct Validator {
+    url: String,
+    deploy_client: DeployServiceClient<Channel>,
+    propose_client: ProposeServiceClient<Channel>,
+    healthy: AtomicBool,
+}
+
+impl Validator {
+    const fn new(
+        url: String,
+        deploy_client: DeployServiceClient<Channel>,
+        propose_client: ProposeServiceClient<Channel>,
+    ) -> Self {
+        Self {
+            url,
+            deploy_client,
+            propose_client,
+            healthy: AtomicBool::new(true),
+        }
+    }
+
+    fn set_healthy(&self, healthy: bool) {
+        if self.healthy.swap(healthy, Ordering::Relaxed) != healthy {
+            if healthy {
+                tracing::info!(validator = self.url, "validator is back");
+            } else {
+                tracing::warn!(validator = self.url, "validator is unhealthy");
+            }
+        }
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+}
 
 #[derive(Clone)]
 pub struct WriteNodeClient {
-    deploy_client: DeployServiceClient<tonic::transport::Channel>,
-    propose_client: ProposeServiceClient<tonic::transport::Channel>,
+    validators: Arc<[Validator]>,
+    /// Validator requests go to, only changed on failover.
+    active: Arc<AtomicUsize>,
+    /// Validator this client is bound to, see [`Self::for_deploy`].
+    pinned: Option<usize>,
+    /// Validator each deploy not proposed yet was accepted by.
+    placements: Arc<Mutex<HashMap<DeployId, usize>>>,
 }
 
 impl WriteNodeClient {
     pub async fn new(
         deploy_service_url: String,
         propose_service_url: String,
-    ) -> anyhow::Result<Self> {
-        let deploy_client = DeployServiceClient::connect(deploy_service_url)
-            .await
-            .context("failed to connect to deploy service")?;
-
-        let propose_client = ProposeServiceClient::connect(propose_service_url)
-            .await
-            .context("failed to connect to propose service")?;
+    ) -> Result<Self, WriteNodeError> {
+        let deploy_client = DeployServiceClient::connect(deploy_service_url.clone()).await?;
+        let propose_client = ProposeServiceClient::connect(propose_service_url).await?;
 
-        Ok(Self {
+        Ok(Self::from_validators(Arc::new([Validator::new(
+            deploy_service_url,
             deploy_client,
             propose_client,
+        )])))
+    }
+
+    /// Sends requests to one validator out of `validators`, given as deploy and propose service
+    /// urls of each, and fails over to the next healthy one when it becomes unreachable.
+    ///
+    /// Deploys are proposed by the validator that accepted them, as deploy pools
+    /// are not shared between validators.
+    /// Connections are established lazily, so validators may be down at construction time.
+    pub async fn with_validators<I>(validators: I) -> Result<Self, WriteNodeError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let connect = |url: String| {
+            Endpoint::from_shared(url)
+                .map(|endpoint| endpoint.connect_timeout(CONNECT_TIMEOUT).connect_lazy())
+        };
+
+        let validators = validators
+            .into_iter()
+            .map(|(deploy_service_url, propose_service_url)| {
+                let deploy_channel = connect(deploy_service_url.clone())?;
+                let propose_channel = connect(propose_service_url)?;
+                Ok(Validator::new(
+                    deploy_service_url,
+                    DeployServiceClient::new(deploy_channel),
+                    ProposeServiceClient::new(propose_channel),
+                ))
+            })
+            .collect::<Result<Arc<[_]>, WriteNodeError>>()?;
+
+        if validators.is_empty() {
+            return Err(WriteNodeError::NoValidators);
+        }
+
+        let client = Self::from_validators(validators);
+        client.check_health().await;
+
+        Ok(client)
+    }
+
+    fn from_validators(validators: Arc<[Validator]>) -> Self {
+        Self {
+            validators,
+            active: Default::default(),
+            pinned: None,
+            placements: Default::default(),
+        }
+    }
+
+    /// Probes every validator with `status` rpc and returns number of healthy ones.
+    pub async fn check_health(&self) -> usize {
+        let probes = self.validators.iter().map(|validator| async move {
+            let healthy = matches!(
+                validator
+                    .deploy_client
+                    .clone()
+                    .status(())
+                    .await
+                    .map(|resp| resp.into_inner().message),
+                Ok(Some(status_response::Message::Status(_)))
+            );
+            validator.set_healthy(healthy);
+            healthy
+        });
+
+        futures::future::join_all(probes)
+            .await
+            .into_iter()
+            .filter(|healthy| *healthy)
+            .count()
+    }
+
+    /// Runs [`Self::check_health`] every `period` until returned handle is aborted.
+    pub fn spawn_health_checks(&self, period: Duration) -> JoinHandle<()> {
+        let client = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            loop {
+                interval.tick().await;
+                client.check_health().await;
+            }
         })
     }
 
+    /// Client bound to the validator that accepted `deploy_id` through this client or its clones,
+    /// so its propose and reads reach the node having the deploy in its pool.
+    /// Deploys already proposed or sent elsewhere bind to the validator requests go to now.
+    #[must_use]
+    pub fn for_deploy(&self, deploy_id: &DeployId) -> Self {
+        let index = self.placement(deploy_id).unwrap_or_else(|| self.select());
+        self.pin(index)
+    }
+
+    /// Deploy service url of the validator requests go to now.
+    pub fn validator_url(&self) -> &str {
+        &self.validators[self.select()].url
+    }
+
+    fn pin(&self, index: usize) -> Self {
+        Self {
+            pinned: Some(index),
+            ..self.clone()
+        }
+    }
+
+    /// Validator that accepted `deploy_id`, unless this client is bound to one already.
+    fn placement(&self, deploy_id: &DeployId) -> Option<usize> {
+        if self.pinned.is_some() {
+            return self.pinned;
+        }
+        lock(&self.placements).get(deploy_id).copied()
+    }
+
+    /// Validator requests go to: the pinned one, otherwise the active one or,
+    /// once it's unhealthy, the next healthy validator, which becomes active.
+    fn select(&self) -> usize {
+        if let Some(index) = self.pinned {
+            return index;
+        }
+
+        let active = self.active.load(Ordering::Relaxed);
+        if self.validators[active].is_healthy() {
+            return active;
+        }
+
+        self.rotation(active)
+            .skip(1)
+            .find(|index| self.validators[*index].is_healthy())
+            .map_or(active, |index| {
+                self.fail_over(active, index);
+                index
+            })
+    }
+
+    /// Indices of all validators, starting from `start`.
+    fn rotation(&self, start: usize) -> impl Iterator<Item = usize> + use<> {
+        let len = self.validators.len();
+        (0..len).map(move |offset| (start + offset) % len)
+    }
+
+    fn fail_over(&self, from: usize, to: usize) {
+        if self
+            .active
+            .compare_exchange(from, to, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            tracing::warn!(
+                from = self.validators[from].url,
+                to = self.validators[to].url,
+                "failing over to another validator"
+            );
+        }
+    }
+
+    /// Sends deploy service request to the selected validator, moving on to the next ones,
+    /// healthy first, while validators are unreachable. Pinned clients never fail over.
+    async fn call<R, T, F, Fut>(&self, request: R, send: F) -> Result<T, WriteNodeError>
+    where
+        R: Clone,
+        F: Fn(DeployServiceClient<Channel>, R) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+    {
+        let start = self.select();
+        let candidates = if self.pinned.is_some() {
+            1
+        } else {
+            self.validators.len()
+        };
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) = self
+            .rotation(start)
+            .take(candidates)
+            .partition(|index| self.validators[*index].is_healthy() || *index == start);
+
+        let mut last_error = None;
+        for index in healthy.into_iter().chain(unhealthy) {
+            let validator = &self.validators[index];
+            match send(validator.deploy_client.clone(), request.clone()).await {
+                Ok(resp) => {
+                    validator.set_healthy(true);
+                    if index != start {
+                        self.fail_over(start, index);
+                    }
+                    return Ok(resp.into_inner());
+                }
+                Err(status) if status.code() == tonic::Code::Unavailable => {
+                    tracing::warn!(validator = validator.url, "validator unreachable: {status}");
+                    validator.set_healthy(false);
+                    last_error = Some(status);
+                }
+                Err(status) => return Err(status.into()),
+            }
+        }
+
+        Err(last_error.map_or(WriteNodeError::NoValidators, Into::into))
+    }
+
+    /// Sends propose service request to the selected validator, no failover, since
+    /// other validators don't have deploys of this one.
+    async fn call_propose<R, T, F, Fut>(
+        &self,
+        request: R,
+        send: F,
+    ) -> Result<(usize, T), WriteNodeError>
+    where
+        F: FnOnce(ProposeServiceClient<Channel>, R) -> Fut,
+        Fut: Future<Output = Result<tonic::Response<T>, tonic::Status>>,
+    {
+        let index = self.select();
+        let resp = send(self.validators[index].propose_client.clone(), request).await?;
+        Ok((index, resp.into_inner()))
+    }
+
+    /// Forgets deploys validator `index` had in its pool once it proposed.
+    fn proposed(&self, index: usize, result: &Result<BlockId, WriteNodeError>) {
+        if matches!(
+            result,
+            Ok(_) | Err(WriteNodeError::ProposeRefused(ProposeRefusal::NoNewDeploys))
+        ) {
+            lock(&self.placements).retain(|_, placement| *placement != index);
+        }
+    }
+
     pub async fn deploy(
         &mut self,
         key: &SecretKey,
         deploy_data: DeployData,
-    ) -> anyhow::Result<DeployId> {
+    ) -> Result<DeployId, WriteNodeError> {
         let valid_after_block_number = match deploy_data.valid_after_block_number {
             ValidAfter::Head => self.get_head_block_index().await?,
             ValidAfter::Index(i) => i,
         };
 
-        let mut msg = DeployDataProto {
+        let contract = firefly_signing::UnsignedDeploy {
             term: deploy_data.term,
             timestamp: deploy_data.timestamp.timestamp_millis(),
-            phlo_price: 1,
+            phlo_price: deploy_data.phlo_price,
             phlo_limit: deploy_data.phlo_limit as _,
             valid_after_block_number: valid_after_block_number as _,
-            shard_id: "root".into(),
-            ..Default::default()
-        };
-
-        let secp = Secp256k1::new();
-
-        let hash = Blake2b::<U32>::new()
-            .chain_update(msg.encode_to_vec())
-            .finalize();
-
-        let signature = secp.sign_ecdsa(Message::from_digest(hash.into()), key);
-
-        msg.sig = signature.serialize_der().to_vec();
-        msg.sig_algorithm = "secp256k1".into();
+            shard_id: deploy_data.shard_id,
+        }
+        .encode();
 
-        let public_key = key.public_key(&secp);
-        msg.deployer = public_key.serialize_uncompressed().into();
-
-        let resp = self
-            .deploy_client
-            .do_deploy(msg)
-            .await?
-            .into_inner()
-            .message
-            .context("missing do_deploy responce")?;
-
-        let deploy_id = match resp {
-            deploy_response::Message::Result(deploy_id) => deploy_id,
-            deploy_response::Message::Error(err) => {
-                return Err(anyhow!("do_deploy error: {err:?}"));
-            }
-        };
-
-        deploy_id
-            .strip_prefix("Success! DeployId is: ")
-            .map(|id| DeployId::from(id.to_owned()))
-            .context("failed to extract deploy_id")
+        self.deploy_signed_contract(SignedCode {
+            sig: firefly_signing::sign_contract(key, &contract),
+            sig_algorithm: firefly_signing::SIG_ALGORITHM.into(),
+            deployer: firefly_signing::deployer(key).into(),
+            contract,
+        })
+        .await
     }
 
     pub async fn deploy_signed_contract(
         &mut self,
         contract: SignedCode,
-    ) -> anyhow::Result<DeployId> {
+    ) -> Result<DeployId, WriteNodeError> {
         let mut msg = DeployDataProto::decode(contract.contract.as_slice())?;
 
         msg.sig = contract.sig;
         msg.sig_algorithm = contract.sig_algorithm;
         msg.deployer = contract.deployer;
 
+        self.do_deploy(msg).await
+    }
+
+    /// Retries transient failures on the validator the first attempt went to.
+    ///
+    /// Node keeps its deploy pool by signature, so resending the same signed bytes to it
+    /// can't make a second deploy. Deploys already in a block would be taken in again though,
+    /// so before resending the node is asked whether it has the deploy in a block.
+    /// A resent deploy the node refuses as duplicate was taken in by a failed attempt.
+    #[tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(deploy_id = hex::encode(&msg.sig), retries = 0)
+    )]
+    async fn do_deploy(&self, msg: DeployDataProto) -> Result<DeployId, WriteNodeError> {
+        let deploy_id: DeployId = hex::encode(&msg.sig).into();
+        let index = self.select();
+        let validator = self.pin(index);
+        let attempts = AtomicUsize::new(0);
+
+        let submit = || async {
+            let resubmission = attempts.fetch_add(1, Ordering::Relaxed) > 0;
+            if resubmission && validator.clone().find_deploy(&deploy_id).await?.is_some() {
+                tracing::info!("deploy is already in a block");
+                return Ok(deploy_id.clone());
+            }
+
+            match validator.send_deploy(msg.clone()).await {
+                Err(WriteNodeError::Service(ServiceError::DuplicateDeploy(_))) if resubmission => {
+                    tracing::info!("deploy is already in the pool");
+                    Ok(deploy_id.clone())
+                }
+                result => result,
+            }
+        };
+
+        let deploy_id = submit
+            .retry(
+                ExponentialBuilder::default()
+                    .with_min_delay(DEPLOY_RETRY_MIN_DELAY)
+                    .with_max_times(DEPLOY_RETRIES),
+            )
+            .when(WriteNodeError::is_transient)
+            .notify(|err, delay| {
+                let retries = attempts.load(Ordering::Relaxed);
+                tracing::Span::current().record("retries", retries);
+                tracing::warn!(
+                    retries,
+                    "deploy submission failed, retrying in {delay:?}: {err}"
+                );
+            })
+            .await?;
+
+        lock(&self.placements).insert(deploy_id.clone(), index);
+        Ok(deploy_id)
+    }
+
+    async fn send_deploy(&self, msg: DeployDataProto) -> Result<DeployId, WriteNodeError> {
         let resp = self
-            .deploy_client
-            .do_deploy(msg)
+            .call(
+                msg,
+                |mut client, msg| async move { client.do_deploy(msg).await },
+            )
             .await?
-            .into_inner()
             .message
-            .context("missing do_deploy responce")?;
+            .ok_or_else(|| missing("do_deploy response"))?;
 
         let deploy_id = match resp {
             deploy_response::Message::Result(deploy_id) => deploy_id,
             deploy_response::Message::Error(err) => {
-                return Err(anyhow!("do_deploy error: {err:?}"));
+                return Err(WriteNodeError::Service(err.into()));
             }
         };
 
         deploy_id
             .strip_prefix("Success! DeployId is: ")
-            .map(|id| DeployId::from(id.to_owned()))
-            .context("failed to extract deploy_id")
+            .map(|id| id.to_owned().into())
+            .ok_or(WriteNodeError::ResponseFormat(deploy_id))
     }
 
-    pub async fn propose(&mut self) -> anyhow::Result<BlockId> {
-        let resp = self
-            .propose_client
-            .propose(ProposeQuery { is_async: false })
-            .await
-            .context("propose grpc error")?
-            .into_inner()
-            .message
-            .context("missing propose responce")?;
+    /// Proposes on the validator requests go to, which is the one deploys went to
+    /// unless it failed over in between, see [`Self::for_deploy`] and [`Self::propose_deployed`].
+    ///
+    /// Proposes aren't retried: one whose response was lost may have created a block already,
+    /// so it's up to the caller to check deploys with [`Self::find_deploy`] and propose again,
+    /// as [`crate::Proposer`] does.
+    pub async fn propose(&mut self) -> Result<BlockId, WriteNodeError> {
+        let (index, resp) = self
+            .call_propose(
+                ProposeQuery { is_async: false },
+                |mut client, query| async move { client.propose(query).await },
+            )
+            .await?;
 
-        let block_hash = match resp {
-            propose_response::Message::Result(block_hash) => block_hash,
-            propose_response::Message::Error(err) => return Err(anyhow!("propose error: {err:?}")),
+        let result = match resp.message.ok_or_else(|| missing("propose response"))? {
+            propose_response::Message::Result(result) => parse_proposed_block(result),
+            propose_response::Message::Error(err) => {
+                Err(WriteNodeError::ProposeRefused(ProposeRefusal::from(err)))
+            }
         };
+        self.proposed(index, &result);
+        result
+    }
+
+    /// Proposes on every validator holding deploys sent through this client or its clones
+    /// that were not proposed yet, returning the created blocks.
+    pub async fn propose_deployed(&self) -> Result<Vec<BlockId>, WriteNodeError> {
+        let holders: BTreeSet<_> = lock(&self.placements).values().copied().collect();
+
+        let mut blocks = Vec::with_capacity(holders.len());
+        for index in holders {
+            blocks.push(self.pin(index).propose().await?);
+        }
+        Ok(blocks)
+    }
 
-        block_hash
-            .strip_prefix("Success! Block ")
-            .and_then(|block_hash| block_hash.strip_suffix(" created and added."))
-            .map(|id| BlockId::from(id.to_owned()))
-            .context("failed to extract block hash")
+    /// Starts propose without waiting for the block, see [`Self::propose_result`].
+    pub async fn propose_async(&mut self) -> Result<(), WriteNodeError> {
+        let (_, resp) = self
+            .call_propose(
+                ProposeQuery { is_async: true },
+                |mut client, query| async move { client.propose(query).await },
+            )
+            .await?;
+
+        match resp.message.ok_or_else(|| missing("propose response"))? {
+            propose_response::Message::Result(_) => Ok(()),
+            propose_response::Message::Error(err) => {
+                Err(WriteNodeError::ProposeRefused(ProposeRefusal::from(err)))
+            }
+        }
+    }
+
+    /// Waits for propose in progress to finish and returns the created block.
+    pub async fn propose_result(&mut self) -> Result<BlockId, WriteNodeError> {
+        let (index, resp) = self
+            .call_propose(ProposeResultQuery {}, |mut client, query| async move {
+                client.propose_result(query).await
+            })
+            .await?;
+
+        let result = match resp
+            .message
+            .ok_or_else(|| missing("propose_result response"))?
+        {
+            propose_result_response::Message::Result(result) => parse_proposed_block(result),
+            propose_result_response::Message::Error(err) => {
+                Err(WriteNodeError::ProposeRefused(ProposeRefusal::from(err)))
+            }
+        };
+        self.proposed(index, &result);
+        result
     }
 
     pub async fn full_deploy(
         &mut self,
         key: &SecretKey,
         deploy_data: DeployData,
-    ) -> anyhow::Result<BlockId> {
-        self.deploy(key, deploy_data).await?;
-        self.propose().await
+    ) -> Result<BlockId, WriteNodeError> {
+        let deploy_id = self.deploy(key, deploy_data).await?;
+        self.for_deploy(&deploy_id).propose().await
     }
 
-    pub async fn get_head_block_index(&mut self) -> anyhow::Result<u64> {
+    pub async fn get_head_block_index(&mut self) -> Result<u64, WriteNodeError> {
         let mut stream = self
-            .deploy_client
-            .show_main_chain(BlocksQuery { depth: 1 })
-            .await?
-            .into_inner();
+            .call(BlocksQuery { depth: 1 }, |mut client, query| async move {
+                client.show_main_chain(query).await
+            })
+            .await?;
 
         stream
             .try_next()
@@ -174,7 +568,7 @@ impl WriteNodeClient {
             .and_then(|block| block.message)
             .map_or(Ok(0), |m| match m {
                 block_info_response::Message::Error(err) => {
-                    Err(anyhow!("show_main_chain error: {err:?}"))
+                    Err(WriteNodeError::Service(err.into()))
                 }
                 block_info_response::Message::BlockInfo(light_block_info) => {
                     Ok(light_block_info.block_number as _)
@@ -182,53 +576,527 @@ impl WriteNodeClient {
             })
     }
 
+    /// Returns blocks with numbers in `start..=end` range.
+    pub async fn get_blocks_by_heights(
+        &mut self,
+        start: u64,
+        end: u64,
+    ) -> Result<Vec<LightBlockInfo>, WriteNodeError> {
+        let query = BlocksQueryByHeight {
+            start_block_number: start as _,
+            end_block_number: end as _,
+        };
+        let stream = self
+            .call(query, |mut client, query| async move {
+                client.get_blocks_by_heights(query).await
+            })
+            .await?;
+
+        collect_light_blocks(stream, "get_blocks_by_heights response").await
+    }
+
+    /// Returns blocks of the `depth` latest heights, including ones off the main chain.
+    pub async fn get_blocks(&mut self, depth: u32) -> Result<Vec<LightBlockInfo>, WriteNodeError> {
+        let query = BlocksQuery { depth: depth as _ };
+        let stream = self
+            .call(query, |mut client, query| async move {
+                client.get_blocks(query).await
+            })
+            .await?;
+
+        collect_light_blocks(stream, "get_blocks response").await
+    }
+
+    /// Returns `depth` latest blocks of the main chain, newest first.
+    pub async fn show_main_chain(
+        &mut self,
+        depth: u32,
+    ) -> Result<Vec<LightBlockInfo>, WriteNodeError> {
+        let query = BlocksQuery { depth: depth as _ };
+        let stream = self
+            .call(query, |mut client, query| async move {
+                client.show_main_chain(query).await
+            })
+            .await?;
+
+        collect_light_blocks(stream, "show_main_chain response").await
+    }
+
+    /// Returns DAG of `depth` heights starting from `start_block_number` in graphviz dot format.
+    pub async fn visualize_dag(
+        &mut self,
+        depth: u32,
+        start_block_number: u32,
+    ) -> Result<String, WriteNodeError> {
+        let query = VisualizeDagQuery {
+            depth: depth as _,
+            show_justification_lines: true,
+            start_block_number: start_block_number as _,
+        };
+        let stream = self
+            .call(query, |mut client, query| async move {
+                client.visualize_dag(query).await
+            })
+            .await?;
+
+        stream
+            .map_err(WriteNodeError::from)
+            .and_then(|resp| async move {
+                match resp
+                    .message
+                    .ok_or_else(|| missing("visualize_dag response"))?
+                {
+                    visualize_blocks_response::Message::Content(content) => Ok(content),
+                    visualize_blocks_response::Message::Error(err) => {
+                        Err(WriteNodeError::Service(err.into()))
+                    }
+                }
+            })
+            .try_collect()
+            .await
+    }
+
+    /// Returns block along with events produced by its deploys.
+    pub async fn get_event_by_hash(
+        &mut self,
+        hash: &BlockId,
+    ) -> Result<BlockEventInfo, WriteNodeError> {
+        let query = ReportQuery {
+            hash: hash.to_string(),
+            force_replay: false,
+        };
+        let resp = self
+            .call(query, |mut client, query| async move {
+                client.get_event_by_hash(query).await
+            })
+            .await?
+            .message
+            .ok_or_else(|| missing("get_event_by_hash response"))?;
+
+        match resp {
+            event_info_response::Message::Result(info) => Ok(info),
+            event_info_response::Message::Error(err) => Err(WriteNodeError::Service(err.into())),
+        }
+    }
+
+    pub async fn find_deploy(
+        &mut self,
+        deploy_id: &DeployId,
+    ) -> Result<Option<LightBlockInfo>, WriteNodeError> {
+        let raw_deploy_id = hex::decode(deploy_id.as_ref())
+            .map_err(|_| WriteNodeError::InvalidDeployId(deploy_id.to_string()))?;
+
+        let query = FindDeployQuery {
+            deploy_id: raw_deploy_id,
+        };
+        let holder = self
+            .placement(deploy_id)
+            .map_or_else(|| self.clone(), |index| self.pin(index));
+        let resp = holder
+            .call(query, |mut client, query| async move {
+                client.find_deploy(query).await
+            })
+            .await?
+            .message
+            .ok_or_else(|| missing("find_deploy response"))?;
+
+        match resp {
+            find_deploy_response::Message::BlockInfo(block_info) => Ok(Some(block_info)),
+            find_deploy_response::Message::Error(err) => match err.into() {
+                ServiceError::DeployNotFound(_) => Ok(None),
+                err => Err(WriteNodeError::Service(err)),
+            },
+        }
+    }
+
+    pub async fn is_finalized(&mut self, hash: &BlockId) -> Result<bool, WriteNodeError> {
+        let query = IsFinalizedQuery {
+            hash: hash.to_string(),
+        };
+        let resp = self
+            .call(query, |mut client, query| async move {
+                client.is_finalized(query).await
+            })
+            .await?
+            .message
+            .ok_or_else(|| missing("is_finalized response"))?;
+
+        match resp {
+            is_finalized_response::Message::IsFinalized(is_finalized) => Ok(is_finalized),
+            is_finalized_response::Message::Error(err) => Err(WriteNodeError::Service(err.into())),
+        }
+    }
+
+    /// Returns first `count` unforgeable names a deploy signed by `deployer` with `timestamp`
+    /// creates, `deployer` being public key bytes as in the deploy.
+    pub async fn preview_private_names(
+        &mut self,
+        deployer: &[u8],
+        timestamp: i64,
+        count: u32,
+    ) -> Result<Vec<UnforgeableName>, WriteNodeError> {
+        let query = PrivateNamePreviewQuery {
+            user: deployer.to_vec(),
+            timestamp,
+            name_qty: count as _,
+        };
+        let resp = self
+            .call(query, |mut client, query| async move {
+                client.preview_private_names(query).await
+            })
+            .await?
+            .message
+            .ok_or_else(|| missing("preview_private_names response"))?;
+
+        match resp {
+            private_name_preview_response::Message::Payload(payload) => Ok(payload
+                .ids
+                .into_iter()
+                .map(UnforgeableName::Private)
+                .collect()),
+            private_name_preview_response::Message::Error(err) => {
+                Err(WriteNodeError::Service(err.into()))
+            }
+        }
+    }
+
+    /// Returns first `count` unforgeable names the signed deploy creates, so they can be
+    /// read with [`Self::get_channel_value`] once the deploy is in a block.
+    pub async fn preview_deploy_names(
+        &mut self,
+        contract: &SignedCode,
+        count: u32,
+    ) -> Result<Vec<UnforgeableName>, WriteNodeError> {
+        let msg = DeployDataProto::decode(contract.contract.as_slice())?;
+        self.preview_private_names(&contract.deployer, msg.timestamp, count)
+            .await
+    }
+
+    pub async fn last_finalized_block(&mut self) -> Result<BlockInfo, WriteNodeError> {
+        let resp = self
+            .call(LastFinalizedBlockQuery {}, |mut client, query| async move {
+                client.last_finalized_block(query).await
+            })
+            .await?
+            .message
+            .ok_or_else(|| missing("last_finalized_block response"))?;
+
+        match resp {
+            last_finalized_block_response::Message::BlockInfo(block_info) => Ok(block_info),
+            last_finalized_block_response::Message::Error(err) => {
+                Err(WriteNodeError::Service(err.into()))
+            }
+        }
+    }
+
+    pub async fn get_block(&mut self, hash: &BlockId) -> Result<BlockInfo, WriteNodeError> {
+        let query = BlockQuery {
+            hash: hash.to_string(),
+        };
+        let resp = self
+            .call(query, |mut client, query| async move {
+                client.get_block(query).await
+            })
+            .await?
+            .message
+            .ok_or_else(|| missing("get_block response"))?;
+
+        match resp {
+            block_response::Message::BlockInfo(block_info) => Ok(block_info),
+            block_response::Message::Error(err) => Err(WriteNodeError::Service(err.into())),
+        }
+    }
+
+    /// Resolves deploy state from the node, without relying on [`crate::NodeEvents`].
+    /// Deploys sent through this client are looked up on the validator that accepted them.
+    pub async fn deploy_status(
+        &mut self,
+        deploy_id: &DeployId,
+    ) -> Result<DeployStatus, WriteNodeError> {
+        let mut holder = self
+            .placement(deploy_id)
+            .map_or_else(|| self.clone(), |index| self.pin(index));
+
+        let Some(block) = holder.find_deploy(deploy_id).await? else {
+            return Ok(DeployStatus::Pending);
+        };
+
+        let block_id: BlockId = block.block_hash.into();
+        if !holder.is_finalized(&block_id).await? {
+            return Ok(DeployStatus::InBlock(block_id));
+        }
+
+        let deploy = holder
+            .get_block(&block_id)
+            .await?
+            .deploys
+            .into_iter()
+            .find(|deploy| &deploy.sig == deploy_id.as_ref())
+            .ok_or_else(|| missing("deploy in get_block"))?;
+
+        Ok(DeployStatus::Finalized {
+            cost: deploy.cost,
+            errored: deploy.errored,
+        })
+    }
+
+    /// Returns every datum at `name` in the post-state of block `hash`, in the order the node gives them.
     pub async fn get_channel_value<T>(
         &mut self,
         hash: BlockId,
-        channel: String,
-    ) -> anyhow::Result<T>
+        name: impl Into<Par>,
+    ) -> Result<Vec<ChannelData<T>>, WriteNodeError>
     where
         T: FromExpr,
     {
-        let mut par = Par::default();
-        par.exprs.push(Expr {
-            expr_instance: Some(ExprInstance::GString(channel)),
-        });
+        let (block, pars) = self.data_at_name(hash, name.into()).await?;
+        decode_channel_data(&block, pars)
+    }
 
+    async fn data_at_name(
+        &self,
+        hash: BlockId,
+        name: Par,
+    ) -> Result<(LightBlockInfo, Vec<Par>), WriteNodeError> {
+        let query = DataAtNameByBlockQuery {
+            par: Some(name),
+            block_hash: hash.into(),
+            use_pre_state_hash: false,
+        };
         let resp = self
-            .deploy_client
-            .get_data_at_name(DataAtNameByBlockQuery {
-                par: Some(par),
-                block_hash: hash.into(),
-                use_pre_state_hash: false,
+            .call(query, |mut client, query| async move {
+                client.get_data_at_name(query).await
             })
-            .await
-            .context("get_data_at_name grpc error")?
-            .into_inner()
+            .await?
             .message
-            .context("missing get_data_at_name responce")?;
+            .ok_or_else(|| missing("get_data_at_name response"))?;
 
         let payload = match resp {
             rho_data_response::Message::Payload(payload) => payload,
             rho_data_response::Message::Error(err) => {
-                return Err(anyhow!("get_data_at_name error: {err:?}"));
+                return Err(WriteNodeError::Service(err.into()));
             }
         };
 
-        let par = payload
-            .par
-            .into_iter()
-            .next_back()
-            .context("missing par in get_data_at_name")?;
-        let expr = par
-            .exprs
+        let block = payload
+            .block
+            .ok_or_else(|| missing("block in get_data_at_name"))?;
+        Ok((block, payload.par))
+    }
+
+    /// Returns data at `name` for `depth` latest blocks of the main chain that touched it.
+    ///
+    /// Data is the post-state of each block, so a datum staying on the channel
+    /// is repeated in every such block.
+    pub async fn listen_for_data_at_name(
+        &mut self,
+        name: impl Into<Par>,
+        depth: u32,
+    ) -> Result<Vec<DataWithBlockInfo>, WriteNodeError> {
+        let query = DataAtNameQuery {
+            depth: depth as _,
+            name: Some(name.into()),
+        };
+        let resp = self
+            .call(query, |mut client, query| async move {
+                client.listen_for_data_at_name(query).await
+            })
+            .await?
+            .message
+            .ok_or_else(|| missing("listen_for_data_at_name response"))?;
+
+        match resp {
+            listening_name_data_response::Message::Payload(payload) => Ok(payload.block_info),
+            listening_name_data_response::Message::Error(err) => {
+                Err(WriteNodeError::Service(err.into()))
+            }
+        }
+    }
+
+    /// Follows `name`, yielding data at it in the post-state of main chain blocks
+    /// starting from `from_height`, in block order.
+    ///
+    /// Post-state is yielded only when it differs from the previous block,
+    /// so a datum staying on the channel is yielded once rather than for every block.
+    /// The node is polled every `interval`. Errors are yielded as they happen
+    /// and the next poll resumes from the first height not yet yielded.
+    pub fn watch_channel<T>(
+        &self,
+        name: Par,
+        from_height: u64,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<ChannelData<T>, WriteNodeError>> + use<T>
+    where
+        T: FromExpr,
+    {
+        let cursor = ChannelCursor {
+            next_height: from_height,
+            snapshot: None,
+        };
+        let state = (self.clone(), cursor, VecDeque::new(), true);
+
+        stream::unfold(
+            state,
+            move |(mut client, mut cursor, mut ready, mut first)| {
+                let name = name.clone();
+                async move {
+                    loop {
+                        if let Some(data) = ready.pop_front() {
+                            return Some((Ok(data), (client, cursor, ready, first)));
+                        }
+
+                        if !first {
+                            tokio::time::sleep(interval).await;
+                        }
+                        first = false;
+
+                        if let Err(err) = client
+                            .channel_data_since(&name, &mut cursor, &mut ready)
+                            .await
+                        {
+                            return Some((Err(err), (client, cursor, ready, first)));
+                        }
+                    }
+                }
+            },
+        )
+    }
+
+    /// Queues data at `name` in main chain blocks from the cursor on, at most [`MAX_QUERY_DEPTH`] heights per call.
+    ///
+    /// The cursor moves past every height handled, so data queued before an error is kept.
+    /// Heights above the last finalized block without a main chain block are retried on the next call,
+    /// the block there may not have been picked yet.
+    async fn channel_data_since<T>(
+        &mut self,
+        name: &Par,
+        cursor: &mut ChannelCursor,
+        ready: &mut VecDeque<ChannelData<T>>,
+    ) -> Result<(), WriteNodeError>
+    where
+        T: FromExpr,
+    {
+        let main_chain = self.show_main_chain(MAX_QUERY_DEPTH).await?;
+        let Some(head) = main_chain
+            .iter()
+            .map(|block| block.block_number as u64)
+            .max()
+        else {
+            return Ok(());
+        };
+        if head < cursor.next_height {
+            return Ok(());
+        }
+        let main_chain: HashSet<_> = main_chain
             .into_iter()
-            .next()
-            .context("missing exprs in get_data_at_name")?;
-        let expr = expr
-            .expr_instance
-            .context("missing expr_instance in get_data_at_name")?;
+            .map(|block| block.block_hash)
+            .collect();
+
+        let last_finalized = self
+            .last_finalized_block()
+            .await?
+            .block_info
+            .ok_or_else(|| missing("block_info in last_finalized_block"))?
+            .block_number as u64;
+
+        let from = cursor.next_height;
+        let depth: u64 = MAX_QUERY_DEPTH.into();
+        let to = head.min(from.saturating_add(depth - 1));
+        let mut by_height: BTreeMap<u64, Vec<LightBlockInfo>> = BTreeMap::new();
+        for block in self.get_blocks_by_heights(from, to).await? {
+            by_height
+                .entry(block.block_number as u64)
+                .or_default()
+                .push(block);
+        }
 
-        T::from(expr)
+        for height in from..=to {
+            let mut selected = None;
+            for block in by_height.remove(&height).unwrap_or_default() {
+                let block_id: BlockId = block.block_hash.clone().into();
+                if main_chain.contains(&block.block_hash) || self.is_finalized(&block_id).await? {
+                    selected = Some(block_id);
+                    break;
+                }
+            }
+
+            match selected {
+                Some(block_id) => {
+                    let (block, pars) = self.data_at_name(block_id, name.clone()).await?;
+                    if cursor.snapshot.as_ref() != Some(&pars) {
+                        ready.extend(decode_channel_data(&block, pars.clone())?);
+                    }
+                    cursor.snapshot = Some(pars);
+                }
+                None if height > last_finalized => return Ok(()),
+                None => {}
+            }
+            cursor.next_height = height.saturating_add(1);
+        }
+
+        Ok(())
     }
 }
+
+/// Where [`WriteNodeClient::watch_channel`] resumes polling.
+struct ChannelCursor {
+    next_height: u64,
+    /// Data at the name in the last block handled.
+    snapshot: Option<Vec<Par>>,
+}
+
+fn decode_channel_data<T>(
+    block: &LightBlockInfo,
+    pars: Vec<Par>,
+) -> Result<Vec<ChannelData<T>>, WriteNodeError>
+where
+    T: FromExpr,
+{
+    pars.into_iter()
+        .map(|par| {
+            Ok(ChannelData {
+                block: block.block_hash.clone().into(),
+                block_number: block.block_number as _,
+                value: decode_par(par)?,
+            })
+        })
+        .collect()
+}
+
+async fn collect_light_blocks(
+    stream: tonic::Streaming<BlockInfoResponse>,
+    what: &str,
+) -> Result<Vec<LightBlockInfo>, WriteNodeError> {
+    stream
+        .map_err(WriteNodeError::from)
+        .and_then(|block| async move {
+            match block.message.ok_or_else(|| missing(what))? {
+                block_info_response::Message::BlockInfo(light_block_info) => Ok(light_block_info),
+                block_info_response::Message::Error(err) => {
+                    Err(WriteNodeError::Service(err.into()))
+                }
+            }
+        })
+        .try_collect()
+        .await
+}
+
+fn parse_proposed_block(result: String) -> Result<BlockId, WriteNodeError> {
+    result
+        .strip_prefix("Success! Block ")
+        .and_then(|block_hash| block_hash.strip_suffix(" created and added."))
+        .map(|id| id.to_owned().into())
+        .ok_or(WriteNodeError::ResponseFormat(result))
+}
+
+fn decode_par<T: FromExpr>(par: Par) -> Result<T, WriteNodeError> {
+    T::from_par(par).map_err(WriteNodeError::Decoding)
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn missing(what: &str) -> WriteNodeError {
+    WriteNodeError::ResponseFormat(format!("missing {what}"))
+}
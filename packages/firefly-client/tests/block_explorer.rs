@@ -0,0 +1,70 @@
+use firefly_client::BlockExplorer;
+use firefly_client::fake_node::{DeployExecution, FakeNode};
+use firefly_client::models::DeployData;
+use futures::TryStreamExt;
+use secp256k1::{Secp256k1, SecretKey};
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn test_blocks_by_heights() {
+    let node = FakeNode::start().await.unwrap();
+    node.on_deploy(|_| DeployExecution {
+        cost: 7,
+        errored: false,
+    });
+    let mut client = node.write_client().await.unwrap();
+
+    let mut deploy_ids = vec![];
+    for term in ["1", "2"] {
+        let deploy_id = client
+            .deploy(&key(), DeployData::builder(term.into()).build())
+            .await
+            .unwrap();
+        client.propose().await.unwrap();
+        deploy_ids.push(deploy_id);
+    }
+
+    let explorer = BlockExplorer::new(client);
+    let blocks: Vec<_> = explorer
+        .blocks_by_heights(0..=2)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(blocks.len(), 3);
+    assert!(blocks.iter().all(|block| block.summary.finalized));
+    assert_eq!(
+        blocks
+            .iter()
+            .map(|block| block.summary.number)
+            .collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    assert!(blocks[0].deploys.is_empty());
+
+    let deploy = &blocks[2].deploys[0];
+    assert_eq!(deploy.id, deploy_ids[1]);
+    assert_eq!(deploy.term, "2");
+    assert_eq!(deploy.cost, 7);
+    assert_eq!(deploy.deployer, key().public_key(&Secp256k1::new()));
+
+    let block = explorer.block(&blocks[1].summary.hash).await.unwrap();
+    assert_eq!(block, blocks[1]);
+}
+
+#[tokio::test]
+async fn test_blocks_by_heights_near_max_height() {
+    let node = FakeNode::start().await.unwrap();
+    let explorer = BlockExplorer::new(node.write_client().await.unwrap());
+
+    let blocks: Vec<_> = explorer
+        .blocks_by_heights(u64::MAX - 1..=u64::MAX)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert!(blocks.is_empty());
+}
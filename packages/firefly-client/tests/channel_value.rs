@@ -0,0 +1,78 @@
+use firefly_client::fake_node::FakeNode;
+use firefly_client::models::rhoapi::Par;
+use firefly_client::models::{ChannelData, DeployData, UnforgeableName};
+use firefly_client::rendering::Value;
+use secp256k1::SecretKey;
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn test_get_channel_value_at_deploy_id_tuple() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+
+    let deploy_id = client
+        .deploy(&key(), DeployData::builder("Nil".into()).build())
+        .await
+        .unwrap();
+
+    let name = || {
+        Par::tuple([
+            UnforgeableName::try_from(&deploy_id).unwrap().into(),
+            "result".into(),
+        ])
+    };
+    node.set_data_at_name(name(), [Value::Int(1), Value::Int(2)]);
+    let block = client.propose().await.unwrap();
+
+    let data = client
+        .get_channel_value::<i64>(block.clone(), name())
+        .await
+        .unwrap();
+    assert_eq!(
+        data.iter().map(|data| data.value).collect::<Vec<_>>(),
+        [1, 2]
+    );
+    assert!(data.iter().all(|data| data.block == block));
+
+    let other: Vec<ChannelData<i64>> = client.get_channel_value(block, "result").await.unwrap();
+    assert!(other.is_empty());
+}
+
+#[tokio::test]
+async fn test_read_previewed_name() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+
+    let deployer = [4; 65];
+    let names = client
+        .preview_private_names(&deployer, 1_000, 2)
+        .await
+        .unwrap();
+    assert_eq!(names.len(), 2);
+    assert_ne!(names[0], names[1]);
+    assert_eq!(
+        client
+            .preview_private_names(&deployer, 1_000, 1)
+            .await
+            .unwrap(),
+        names[..1]
+    );
+
+    node.set_data_at_name(
+        names[0].clone(),
+        [Value::Map([("answer".to_owned(), Value::Int(42))].into())],
+    );
+    client
+        .deploy(&key(), DeployData::builder("Nil".into()).build())
+        .await
+        .unwrap();
+    let block = client.propose().await.unwrap();
+    let data = client
+        .get_channel_value::<serde_json::Value>(block, names[0].clone())
+        .await
+        .unwrap();
+    assert_eq!(data[0].value, serde_json::json!({ "answer": 42 }));
+}
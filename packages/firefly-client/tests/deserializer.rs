@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use firefly_client::deserializer::from_par;
+use firefly_client::models::rhoapi::expr::ExprInstance;
+use firefly_client::models::rhoapi::g_unforgeable::UnfInstance;
+use firefly_client::models::rhoapi::{
+    EList,
+    EMap,
+    ETuple,
+    Expr,
+    GPrivate,
+    GUnforgeable,
+    KeyValuePair,
+    Par,
+};
+use serde::Deserialize;
+
+fn par(expr: ExprInstance) -> Par {
+    Par {
+        exprs: vec![Expr {
+            expr_instance: Some(expr),
+        }],
+        ..Default::default()
+    }
+}
+
+fn string(value: &str) -> Par {
+    par(ExprInstance::GString(value.to_owned()))
+}
+
+fn map(entries: Vec<(&str, Par)>) -> Par {
+    par(ExprInstance::EMapBody(EMap {
+        kvs: entries
+            .into_iter()
+            .map(|(key, value)| KeyValuePair {
+                key: Some(string(key)),
+                value: Some(value),
+            })
+            .collect(),
+        ..Default::default()
+    }))
+}
+
+#[test]
+fn test_deserialize_primitives() {
+    assert!(from_par::<bool>(par(ExprInstance::GBool(true))).unwrap());
+    assert_eq!(from_par::<u32>(par(ExprInstance::GInt(7))).unwrap(), 7);
+    assert!(from_par::<u32>(par(ExprInstance::GInt(-7))).is_err());
+    assert_eq!(
+        from_par::<String>(par(ExprInstance::GUri("rho:id:foo".into()))).unwrap(),
+        "rho:id:foo"
+    );
+}
+
+#[test]
+fn test_deserialize_option() {
+    assert_eq!(from_par::<Option<String>>(Par::default()).unwrap(), None);
+    assert_eq!(
+        from_par::<Option<String>>(string("foo")).unwrap(),
+        Some("foo".to_owned())
+    );
+}
+
+#[test]
+fn test_deserialize_bytes() {
+    let bytes = par(ExprInstance::GByteArray(vec![0xab, 0xcd]));
+
+    assert_eq!(from_par::<String>(bytes.clone()).unwrap(), "abcd");
+    assert_eq!(
+        from_par::<serde_bytes_like::Bytes>(bytes).unwrap().0,
+        vec![0xab, 0xcd]
+    );
+}
+
+#[test]
+fn test_deserialize_unforgeable() {
+    let par = Par {
+        unforgeables: vec![GUnforgeable {
+            unf_instance: Some(UnfInstance::GPrivateBody(GPrivate { id: vec![1, 2] })),
+        }],
+        ..Default::default()
+    };
+
+    assert_eq!(from_par::<String>(par).unwrap(), "0102");
+}
+
+#[test]
+fn test_deserialize_seq_and_tuple() {
+    let list = par(ExprInstance::EListBody(EList {
+        ps: vec![string("foo"), string("bar")],
+        ..Default::default()
+    }));
+    assert_eq!(from_par::<Vec<String>>(list).unwrap(), vec!["foo", "bar"]);
+
+    let tuple = par(ExprInstance::ETupleBody(ETuple {
+        ps: vec![string("foo"), par(ExprInstance::GInt(1))],
+        ..Default::default()
+    }));
+    assert_eq!(
+        from_par::<(String, i64)>(tuple).unwrap(),
+        ("foo".to_owned(), 1)
+    );
+}
+
+#[test]
+fn test_deserialize_struct() {
+    #[derive(Debug, PartialEq, Eq, Deserialize)]
+    struct Struct {
+        name: String,
+        description: Option<String>,
+        tags: HashMap<String, i64>,
+    }
+
+    let result = from_par::<Struct>(map(vec![
+        ("name", string("foo")),
+        ("description", Par::default()),
+        ("tags", map(vec![("bar", par(ExprInstance::GInt(1)))])),
+    ]))
+    .unwrap();
+
+    assert_eq!(
+        result,
+        Struct {
+            name: "foo".into(),
+            description: None,
+            tags: std::iter::once(("bar".to_owned(), 1)).collect(),
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_enum() {
+    #[derive(Debug, PartialEq, Eq, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Enum {
+        Unit,
+        NewType(String),
+        Struct { name: String },
+    }
+
+    assert_eq!(from_par::<Enum>(string("unit")).unwrap(), Enum::Unit);
+
+    let new_type = par(ExprInstance::ETupleBody(ETuple {
+        ps: vec![string("newtype"), string("foo")],
+        ..Default::default()
+    }));
+    assert_eq!(
+        from_par::<Enum>(new_type).unwrap(),
+        Enum::NewType("foo".into())
+    );
+
+    let r#struct = map(vec![("struct", map(vec![("name", string("foo"))]))]);
+    assert_eq!(
+        from_par::<Enum>(r#struct).unwrap(),
+        Enum::Struct { name: "foo".into() }
+    );
+}
+
+mod serde_bytes_like {
+    use serde::de::{Deserializer, Visitor};
+
+    pub struct Bytes(pub Vec<u8>);
+
+    impl<'de> serde::Deserialize<'de> for Bytes {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct BytesVisitor;
+
+            impl Visitor<'_> for BytesVisitor {
+                type Value = Bytes;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str("bytes")
+                }
+
+                fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                    Ok(Bytes(v))
+                }
+            }
+
+            deserializer.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+}
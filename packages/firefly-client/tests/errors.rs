@@ -0,0 +1,64 @@
+use firefly_client::errors::ServiceError;
+use firefly_client::models::servicemodelapi;
+
+fn classify(message: &str) -> ServiceError {
+    servicemodelapi::ServiceError {
+        messages: vec![message.to_owned()],
+    }
+    .into()
+}
+
+#[test]
+fn test_deploy_not_found() {
+    let message = "Couldn't find block containing deploy with id: 3045022100ab";
+    assert_eq!(
+        classify(message),
+        ServiceError::DeployNotFound(message.to_owned())
+    );
+}
+
+#[test]
+fn test_invalid_signature() {
+    for message in ["Invalid signature", "Invalid signing algorithm"] {
+        assert_eq!(
+            classify(message),
+            ServiceError::InvalidSignature(message.to_owned())
+        );
+    }
+}
+
+#[test]
+fn test_insufficient_phlo() {
+    let message = "Phlo price 0 is less than minimum price 1.";
+    assert_eq!(
+        classify(message),
+        ServiceError::InsufficientPhlo(message.to_owned())
+    );
+}
+
+#[test]
+fn test_expired() {
+    let message = "Deploy validAfterBlockNumber 10 is already out of the deploy lifespan";
+    assert_eq!(classify(message), ServiceError::Expired(message.to_owned()));
+}
+
+#[test]
+fn test_duplicate_deploy() {
+    let message = "Duplicate deploy 3045022100ab";
+    assert_eq!(
+        classify(message),
+        ServiceError::DuplicateDeploy(message.to_owned())
+    );
+}
+
+#[test]
+fn test_other() {
+    for message in [
+        "Deploy shardId 'test' is not as expected network shard 'root'.",
+        "Parsing error: syntax error(\"}\") at 1:5-1:6",
+        "Casper instance not available yet",
+        "Block is already being processed",
+    ] {
+        assert_eq!(classify(message), ServiceError::Other(message.to_owned()));
+    }
+}
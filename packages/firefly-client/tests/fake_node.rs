@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use firefly_client::NodeEvents;
+use firefly_client::fake_node::{DeployExecution, FakeNode};
+use firefly_client::models::{DeployData, DeployStatus};
+use firefly_client::node_events::DeployOutcome;
+use firefly_client::rendering::Value;
+use secp256k1::SecretKey;
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn test_deploy_and_propose() {
+    let node = FakeNode::start().await.unwrap();
+    node.on_deploy(|_| DeployExecution {
+        cost: 42,
+        errored: true,
+    });
+    let mut client = node.write_client().await.unwrap();
+
+    let deploy_id = client
+        .deploy(&key(), DeployData::builder("Nil".into()).build())
+        .await
+        .unwrap();
+    assert_eq!(
+        client.deploy_status(&deploy_id).await.unwrap(),
+        DeployStatus::Pending
+    );
+
+    client.propose().await.unwrap();
+    assert_eq!(
+        client.deploy_status(&deploy_id).await.unwrap(),
+        DeployStatus::Finalized {
+            cost: 42,
+            errored: true,
+        }
+    );
+    assert_eq!(client.get_head_block_index().await.unwrap(), 1);
+
+    let deploys = node.deploys();
+    assert_eq!(deploys.len(), 1);
+    assert_eq!(deploys[0].term, "Nil");
+    assert_eq!(hex::encode(&deploys[0].sig), deploy_id.to_string());
+
+    assert!(client.propose().await.is_err());
+}
+
+#[tokio::test]
+async fn test_explore_deploy() {
+    let node = FakeNode::start().await.unwrap();
+    node.on_explore_deploy(|term| {
+        (term == "balance").then(|| Value::Tuple(vec![Value::Bool(true), Value::Int(7)]))
+    });
+
+    let http = node.read_client_http();
+    let grpc = node.read_client_grpc().await.unwrap();
+
+    let expected = (true, 7);
+    assert_eq!(
+        http.get_data::<(bool, i64)>("balance".into())
+            .await
+            .unwrap(),
+        expected
+    );
+    assert_eq!(
+        grpc.get_data::<(bool, i64)>("balance".into())
+            .await
+            .unwrap(),
+        expected
+    );
+    assert!(grpc.get_data::<i64>("other".into()).await.is_err());
+}
+
+#[tokio::test]
+async fn test_events_for_proposed_deploys() {
+    let node = FakeNode::start().await.unwrap();
+    let events = NodeEvents::new(&node.ws_url());
+    let mut client = node.write_client().await.unwrap();
+
+    let deploy_id = client
+        .deploy(&key(), DeployData::builder("Nil".into()).build())
+        .await
+        .unwrap();
+    let waiter = events.wait_for_deploy(&deploy_id, Duration::from_secs(10));
+
+    // give websocket a moment to connect before the block is emitted
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let block_hash = client.propose().await.unwrap();
+
+    assert_eq!(
+        waiter.await,
+        DeployOutcome::Finalized {
+            block_hash,
+            cost: 1,
+            errored: false,
+        }
+    );
+}
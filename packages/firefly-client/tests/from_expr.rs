@@ -0,0 +1,189 @@
+use firefly_client::helpers::FromExpr;
+use firefly_client::models::UnforgeableName;
+use firefly_client::models::rhoapi::expr::ExprInstance;
+use firefly_client::models::rhoapi::g_unforgeable::UnfInstance;
+use firefly_client::models::rhoapi::{
+    EMap,
+    ETuple,
+    Expr,
+    GPrivate,
+    GUnforgeable,
+    KeyValuePair,
+    Par,
+};
+
+fn par(expr: ExprInstance) -> Par {
+    Par {
+        exprs: vec![Expr {
+            expr_instance: Some(expr),
+        }],
+        ..Default::default()
+    }
+}
+
+fn string(value: &str) -> Par {
+    par(ExprInstance::GString(value.to_owned()))
+}
+
+fn tuple(ps: Vec<Par>) -> ExprInstance {
+    ExprInstance::ETupleBody(ETuple {
+        ps,
+        ..Default::default()
+    })
+}
+
+fn map(entries: Vec<(&str, Par)>) -> ExprInstance {
+    ExprInstance::EMapBody(EMap {
+        kvs: entries
+            .into_iter()
+            .map(|(key, value)| KeyValuePair {
+                key: Some(string(key)),
+                value: Some(value),
+            })
+            .collect(),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_deserialize_int_out_of_range() {
+    let result = <u16 as FromExpr>::from(ExprInstance::GInt(-1));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_deserialize_none() {
+    let result = <Option<String> as FromExpr>::from_par(Par::default()).unwrap();
+    assert_eq!(result, None);
+}
+
+#[test]
+fn test_deserialize_unforgeable_name() {
+    let par = Par {
+        unforgeables: vec![GUnforgeable {
+            unf_instance: Some(UnfInstance::GPrivateBody(GPrivate { id: vec![1, 2] })),
+        }],
+        ..Default::default()
+    };
+
+    let result = UnforgeableName::from_par(par).unwrap();
+    assert_eq!(result, UnforgeableName::Private(vec![1, 2]));
+}
+
+#[test]
+fn test_deserialize_newtype_struct() {
+    #[derive(Debug, PartialEq, Eq, FromExpr)]
+    struct NewType(String);
+
+    let result = <NewType as FromExpr>::from(ExprInstance::GString("str".into())).unwrap();
+    assert_eq!(result, NewType("str".into()));
+}
+
+#[test]
+fn test_deserialize_tuple_struct() {
+    #[derive(Debug, PartialEq, Eq, FromExpr)]
+    struct TupleStruct(String, i64);
+
+    let result =
+        <TupleStruct as FromExpr>::from(tuple(vec![string("foo"), par(ExprInstance::GInt(1))]))
+            .unwrap();
+    assert_eq!(result, TupleStruct("foo".into(), 1));
+}
+
+#[test]
+fn test_deserialize_struct() {
+    #[derive(Debug, PartialEq, Eq, FromExpr)]
+    struct Struct {
+        name: String,
+        second_name: Option<String>,
+    }
+
+    let result = <Struct as FromExpr>::from(map(vec![("name", string("foo"))])).unwrap();
+    assert_eq!(
+        result,
+        Struct {
+            name: "foo".into(),
+            second_name: None,
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_struct_missing_field() {
+    #[derive(Debug, FromExpr)]
+    #[allow(dead_code)]
+    struct Struct {
+        name: String,
+    }
+
+    let result = <Struct as FromExpr>::from(map(vec![]));
+    assert_eq!(result.unwrap_err().to_string(), "missing field `name`");
+}
+
+#[test]
+fn test_deserialize_struct_field_attributes() {
+    #[derive(Debug, PartialEq, Eq, FromExpr)]
+    struct Struct {
+        #[from_expr(rename = "type")]
+        kind: String,
+        #[from_expr(skip)]
+        cache: Option<String>,
+    }
+
+    let result =
+        <Struct as FromExpr>::from(map(vec![("type", string("foo")), ("cache", string("bar"))]))
+            .unwrap();
+    assert_eq!(
+        result,
+        Struct {
+            kind: "foo".into(),
+            cache: None,
+        }
+    );
+}
+
+#[test]
+fn test_deserialize_enum() {
+    #[derive(Debug, PartialEq, Eq, FromExpr)]
+    enum Enum {
+        Unit,
+        #[from_expr(rename = "new_type")]
+        NewType(bool),
+        Struct {
+            name: String,
+        },
+    }
+
+    let unit = <Enum as FromExpr>::from(ExprInstance::GString("Unit".into())).unwrap();
+    assert_eq!(unit, Enum::Unit);
+
+    let new_type = <Enum as FromExpr>::from(tuple(vec![
+        string("new_type"),
+        par(ExprInstance::GBool(true)),
+    ]))
+    .unwrap();
+    assert_eq!(new_type, Enum::NewType(true));
+
+    let r#struct = <Enum as FromExpr>::from(tuple(vec![
+        string("Struct"),
+        par(map(vec![("name", string("foo"))])),
+    ]))
+    .unwrap();
+    assert_eq!(r#struct, Enum::Struct { name: "foo".into() });
+}
+
+#[test]
+fn test_deserialize_json_value_with_bytes() {
+    let value = tuple(vec![
+        par(ExprInstance::GByteArray(vec![0xab, 0x01])),
+        par(map(vec![(
+            "key",
+            par(ExprInstance::GByteArray(vec![0xff])),
+        )])),
+    ]);
+
+    assert_eq!(
+        <serde_json::Value as FromExpr>::from(value).unwrap(),
+        serde_json::json!(["ab01", {"key": "ff"}])
+    );
+}
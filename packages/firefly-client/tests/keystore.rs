@@ -0,0 +1,70 @@
+use firefly_client::errors::KeystoreError;
+use firefly_client::keystore::{Kdf, KeySource, Keystore};
+use secp256k1::SecretKey;
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+#[test]
+fn test_decrypt_pbkdf2_test_vector() {
+    let keystore: Keystore = serde_json::from_str(
+        r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#,
+    )
+    .unwrap();
+
+    let key = keystore.decrypt(b"testpassword").unwrap();
+    assert_eq!(
+        hex::encode(key.secret_bytes()),
+        "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+    );
+}
+
+#[test]
+fn test_encrypted_key_is_loaded_from_file() {
+    let kdf = Kdf::Scrypt {
+        dklen: 32,
+        n: 16,
+        r: 8,
+        p: 1,
+        salt: vec![7; 32],
+    };
+    let keystore = Keystore::encrypt(&key(), b"secret", kdf).unwrap();
+    assert!(matches!(
+        keystore.decrypt(b"wrong"),
+        Err(KeystoreError::WrongPassword)
+    ));
+
+    let dir = std::env::temp_dir().join(format!("keystore-{}", uuid::Uuid::now_v7()));
+    std::fs::create_dir(&dir).unwrap();
+    let keystore_path = dir.join("key.json");
+    let password_path = dir.join("password");
+    std::fs::write(&keystore_path, serde_json::to_vec(&keystore).unwrap()).unwrap();
+    std::fs::write(&password_path, "secret\n").unwrap();
+
+    let source: KeySource = serde_json::from_value(serde_json::json!({
+        "keystore": keystore_path,
+        "password_file": password_path,
+    }))
+    .unwrap();
+    let loaded = source.load();
+    std::fs::remove_dir_all(dir).unwrap();
+
+    assert_eq!(loaded.unwrap(), key());
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use firefly_client::WriteNodeClient;
+use firefly_client::fake_node::FakeNode;
+use firefly_client::models::{BlockId, DeployData, DeployId, NodeEvent, NodeEventKind};
+use firefly_client::node_events::{BlockFilter, DeployOutcome, NodeEvents};
+use futures::StreamExt;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+fn public_key(byte: u8) -> PublicKey {
+    SecretKey::from_byte_array([byte; 32])
+        .unwrap()
+        .public_key(&Secp256k1::new())
+}
+
+fn finalised(deployer: PublicKey) -> NodeEvent {
+    serde_json::from_value(serde_json::json!({
+        "event": "block-finalised",
+        "payload": {
+            "block-hash": "abcd",
+            "parent-hashes": ["ef01"],
+            "creator": "02ff",
+            "seq-num": 3,
+            "deploys": [{
+                "id": "1234",
+                "cost": 10,
+                "deployer": deployer.to_string(),
+                "errored": false,
+            }],
+        },
+    }))
+    .unwrap()
+}
+
+#[test]
+fn test_block_filter_by_kind() {
+    let event = finalised(public_key(1));
+
+    assert!(BlockFilter::default().matches(&event));
+    assert!(
+        BlockFilter::builder()
+            .kinds([NodeEventKind::BlockFinalised])
+            .build()
+            .matches(&event)
+    );
+    assert!(
+        !BlockFilter::builder()
+            .kinds([NodeEventKind::BlockAdded, NodeEventKind::BlockCreated])
+            .build()
+            .matches(&event)
+    );
+}
+
+#[test]
+fn test_block_filter_by_deployer() {
+    let event = finalised(public_key(1));
+
+    assert!(
+        BlockFilter::builder()
+            .deployer(public_key(1))
+            .build()
+            .matches(&event)
+    );
+    assert!(
+        !BlockFilter::builder()
+            .deployer(public_key(2))
+            .build()
+            .matches(&event)
+    );
+    assert!(
+        !BlockFilter::builder()
+            .deployer(public_key(1))
+            .build()
+            .matches(&NodeEvent::Started)
+    );
+}
+
+async fn propose(client: &mut WriteNodeClient, term: &str) -> (DeployId, BlockId) {
+    let deploy_id = client
+        .deploy(&key(), DeployData::builder(term.into()).build())
+        .await
+        .unwrap();
+    (deploy_id, client.propose().await.unwrap())
+}
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+fn block_hash(event: &NodeEvent) -> BlockId {
+    event.payload().unwrap().block_hash.clone()
+}
+
+#[tokio::test]
+async fn test_missed_blocks_are_backfilled_once() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+    let events = NodeEvents::with_backfill(&node.ws_url(), client.clone());
+    let mut blocks = events.subscribe_blocks(
+        BlockFilter::builder()
+            .kinds([NodeEventKind::BlockFinalised])
+            .build(),
+    );
+
+    // blocks proposed before websocket connects are never seen
+    for i in 0.. {
+        propose(&mut client, &format!("connect {i}")).await;
+        if tokio::time::timeout(Duration::from_millis(200), blocks.next())
+            .await
+            .is_ok()
+        {
+            break;
+        }
+    }
+
+    node.set_events_paused(true);
+    let (missed_deploy, missed_block) = propose(&mut client, "missed").await;
+    let outcome = events.wait_for_deploy(&missed_deploy, Duration::from_secs(30));
+    node.set_events_paused(false);
+
+    // first backfill after reconnect fails, live block arriving before the retry must not hide the gap
+    node.set_unavailable(true);
+    node.disconnect_events();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    node.set_unavailable(false);
+    let (_, live_block) = propose(&mut client, "live").await;
+
+    assert!(matches!(
+        outcome.await,
+        DeployOutcome::Finalized { block_hash, .. } if block_hash == missed_block
+    ));
+
+    let mut received = vec![];
+    while let Ok(Some(event)) = tokio::time::timeout(Duration::from_secs(3), blocks.next()).await {
+        received.push(block_hash(&event));
+    }
+    received.sort();
+    let mut expected = vec![missed_block, live_block];
+    expected.sort();
+    assert_eq!(received, expected);
+}
+
+#[tokio::test]
+async fn test_gap_longer_than_query_depth_is_backfilled() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+    let events = NodeEvents::with_backfill(&node.ws_url(), client.clone());
+    let mut blocks = events.subscribe_blocks(
+        BlockFilter::builder()
+            .kinds([NodeEventKind::BlockFinalised])
+            .build(),
+    );
+
+    for i in 0.. {
+        propose(&mut client, &format!("connect {i}")).await;
+        if tokio::time::timeout(Duration::from_millis(200), blocks.next())
+            .await
+            .is_ok()
+        {
+            break;
+        }
+    }
+
+    node.set_events_paused(true);
+    let mut missed = vec![];
+    for i in 0..60 {
+        missed.push(propose(&mut client, &format!("missed {i}")).await);
+    }
+    // waiters are registered before the gap is backfilled
+    let mut outcomes = vec![];
+    for (deploy_id, _) in &missed {
+        outcomes.push(events.wait_for_deploy(deploy_id, Duration::from_secs(30)));
+    }
+    node.set_events_paused(false);
+    node.disconnect_events();
+
+    for (outcome, (_, missed_block)) in outcomes.into_iter().zip(&missed) {
+        assert!(matches!(
+            outcome.await,
+            DeployOutcome::Finalized { block_hash, .. } if &block_hash == missed_block
+        ));
+    }
+}
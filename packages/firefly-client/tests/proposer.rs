@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use firefly_client::fake_node::FakeNode;
+use firefly_client::models::DeployData;
+use firefly_client::proposer::ProposerConfig;
+use firefly_client::{Proposer, WriteNodeClient};
+use secp256k1::SecretKey;
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn test_deploys_are_proposed_in_one_block() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+    let proposer = Proposer::start(
+        client.clone(),
+        ProposerConfig::builder()
+            .interval(Duration::from_millis(50))
+            .build(),
+    );
+
+    let mut waiters = vec![];
+    for term in ["1", "2", "3"] {
+        let deploy_id = client
+            .deploy(&key(), DeployData::builder(term.into()).build())
+            .await
+            .unwrap();
+        waiters.push(proposer.propose(deploy_id));
+    }
+
+    let blocks = futures::future::try_join_all(waiters).await.unwrap();
+    assert!(blocks.iter().all(|block| block == &blocks[0]));
+    assert_eq!(node.blocks().len(), 2);
+}
+
+#[tokio::test]
+async fn test_full_batch_is_proposed_before_interval() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+    let proposer = Proposer::start(
+        client.clone(),
+        ProposerConfig::builder()
+            .interval(Duration::from_hours(1))
+            .max_batch(2)
+            .build(),
+    );
+    // first tick of the interval fires right away
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let mut waiters = vec![];
+    for term in ["1", "2"] {
+        let deploy_id = client
+            .deploy(&key(), DeployData::builder(term.into()).build())
+            .await
+            .unwrap();
+        waiters.push(proposer.propose(deploy_id));
+    }
+
+    let blocks = tokio::time::timeout(
+        Duration::from_secs(5),
+        futures::future::try_join_all(waiters),
+    )
+    .await
+    .unwrap()
+    .unwrap();
+    assert_eq!(blocks[0], blocks[1]);
+}
+
+#[tokio::test]
+async fn test_deploys_are_proposed_by_validators_holding_them() {
+    let first = FakeNode::start().await.unwrap();
+    let second = FakeNode::start().await.unwrap();
+    let mut client = WriteNodeClient::with_validators([
+        (first.grpc_url(), first.grpc_url()),
+        (second.grpc_url(), second.grpc_url()),
+    ])
+    .await
+    .unwrap();
+    let proposer = Proposer::start(
+        client.clone(),
+        ProposerConfig::builder()
+            .interval(Duration::from_millis(50))
+            .build(),
+    );
+
+    let first_deploy = client
+        .deploy(&key(), DeployData::builder("1".into()).build())
+        .await
+        .unwrap();
+
+    // second deploy fails over, leaving the first one in the pool of the first validator
+    first.set_unavailable(true);
+    client.check_health().await;
+    let second_deploy = client
+        .deploy(&key(), DeployData::builder("2".into()).build())
+        .await
+        .unwrap();
+    first.set_unavailable(false);
+
+    let blocks = futures::future::try_join(
+        proposer.propose(first_deploy),
+        proposer.propose(second_deploy),
+    )
+    .await
+    .unwrap();
+    assert_ne!(blocks.0, blocks.1);
+    assert_eq!(first.blocks().len(), 2);
+    assert_eq!(second.blocks().len(), 2);
+}
@@ -3,6 +3,7 @@ use std::str::FromStr;
 
 use firefly_client::models::Uri;
 use firefly_client::rendering::*;
+use proptest::prelude::*;
 use secp256k1::PublicKey;
 
 #[test]
@@ -150,6 +151,175 @@ fn test_serialize_struct() {
     assert_eq!(result, expected);
 }
 
+#[test]
+fn test_serialize_struct_field_attributes() {
+    #[derive(IntoValue)]
+    struct Inner {
+        version: i64,
+    }
+
+    #[derive(IntoValue)]
+    struct Struct {
+        #[into_value(rename = "type")]
+        kind: String,
+        #[into_value(skip)]
+        #[allow(dead_code)]
+        cache: String,
+        #[into_value(flatten)]
+        inner: Inner,
+    }
+
+    let result = Struct {
+        kind: "foo".into(),
+        cache: "bar".into(),
+        inner: Inner { version: 1 },
+    }
+    .into_value();
+
+    let expected = Value::Map(BTreeMap::from([
+        ("type".to_owned(), Value::String("foo".into())),
+        ("version".to_owned(), Value::Int(1)),
+    ]));
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_serialize_externally_tagged_enum() {
+    #[derive(IntoValue)]
+    enum Enum {
+        Unit,
+        #[into_value(rename = "new_type")]
+        NewType(bool),
+        Struct {
+            name: String,
+        },
+    }
+
+    assert_eq!(Enum::Unit.into_value(), Value::String("Unit".into()));
+    assert_eq!(
+        Enum::NewType(true).into_value(),
+        Value::Tuple(vec![Value::String("new_type".into()), Value::Bool(true)])
+    );
+    assert_eq!(
+        Enum::Struct { name: "foo".into() }.into_value(),
+        Value::Tuple(vec![
+            Value::String("Struct".into()),
+            Value::Map(BTreeMap::from([(
+                "name".to_owned(),
+                Value::String("foo".into())
+            )])),
+        ])
+    );
+}
+
+#[test]
+fn test_serialize_internally_tagged_enum() {
+    #[derive(IntoValue)]
+    #[into_value(tag = "type")]
+    enum Enum {
+        Unit,
+        Struct { name: String },
+    }
+
+    assert_eq!(
+        Enum::Unit.into_value(),
+        Value::Map(BTreeMap::from([(
+            "type".to_owned(),
+            Value::String("Unit".into())
+        )]))
+    );
+    assert_eq!(
+        Enum::Struct { name: "foo".into() }.into_value(),
+        Value::Map(BTreeMap::from([
+            ("type".to_owned(), Value::String("Struct".into())),
+            ("name".to_owned(), Value::String("foo".into())),
+        ]))
+    );
+}
+
+#[test]
+fn test_serialize_adjacently_tagged_and_untagged_enum() {
+    #[derive(IntoValue)]
+    #[into_value(tag = "type", content = "value")]
+    enum Adjacent {
+        Pair(i64, i64),
+    }
+
+    #[derive(IntoValue)]
+    #[into_value(untagged)]
+    enum Untagged {
+        Pair(i64, i64),
+    }
+
+    let pair = Value::Tuple(vec![Value::Int(1), Value::Int(2)]);
+    assert_eq!(
+        Adjacent::Pair(1, 2).into_value(),
+        Value::Map(BTreeMap::from([
+            ("type".to_owned(), Value::String("Pair".into())),
+            ("value".to_owned(), pair.clone()),
+        ]))
+    );
+    assert_eq!(Untagged::Pair(1, 2).into_value(), pair);
+}
+
+#[test]
+fn test_serialize_std_types() {
+    assert_eq!(7u32.into_value(), Value::Int(7));
+    assert_eq!(Value::try_from(7u64), Ok(Value::Int(7)));
+    assert!(Value::try_from(u64::MAX).is_err());
+    assert_eq!([1u8, 2].into_value(), Value::Bytes(vec![1, 2]));
+    assert_eq!([true].into_value(), Value::List(vec![Value::Bool(true)]));
+    assert_eq!(Box::new(1).into_value(), Value::Int(1));
+    assert_eq!(
+        std::borrow::Cow::Borrowed("foo").into_value(),
+        Value::String("foo".into())
+    );
+    assert_eq!(
+        std::collections::HashSet::from([1]).into_value(),
+        Value::Set([Value::Int(1)].into())
+    );
+    assert_eq!(
+        std::collections::HashMap::from([("foo", 1)]).into_value(),
+        Value::Map(BTreeMap::from([("foo".to_owned(), Value::Int(1))]))
+    );
+}
+
+#[test]
+fn test_serialize_flattened_maps() {
+    #[derive(IntoValue)]
+    #[into_value(tag = "type")]
+    enum Inner {
+        Version { version: i64 },
+    }
+
+    #[derive(IntoValue)]
+    struct Struct {
+        #[into_value(flatten)]
+        inner: Option<Inner>,
+        #[into_value(flatten)]
+        extra: BTreeMap<&'static str, i64>,
+    }
+
+    let result = Struct {
+        inner: Some(Inner::Version { version: 1 }),
+        extra: BTreeMap::from([("count", 2)]),
+    }
+    .into_value();
+    let expected = Value::Map(BTreeMap::from([
+        ("type".to_owned(), Value::String("Version".into())),
+        ("version".to_owned(), Value::Int(1)),
+        ("count".to_owned(), Value::Int(2)),
+    ]));
+    assert_eq!(result, expected);
+
+    let result = Struct {
+        inner: None,
+        extra: BTreeMap::new(),
+    }
+    .into_value();
+    assert_eq!(result, Value::Map(BTreeMap::new()));
+}
+
 #[test]
 fn test_render_nil() {
     assert_eq!(Value::Nil.to_string(), "Nil");
@@ -209,7 +379,11 @@ fn test_render_map() {
 
 #[test]
 fn test_serialize_str_is_escaped() {
-    let cases = [("foo\\", "\"foo\\\\\""), ("\"foo\"", "\"\\\"foo\\\"\"")];
+    let cases = [
+        ("foo\\", "\"foo\\\\\""),
+        ("\"foo\"", "\"\\\"foo\\\"\""),
+        ("foo\nbar\t", "\"foo\\nbar\\t\""),
+    ];
     for (value, expected) in cases {
         let result = value.into_value();
         assert_eq!(result.to_string(), expected);
@@ -236,3 +410,90 @@ fn test_build_uri_from_string() {
         "rho:id:1qw5ehmq1x49dey4eadr1h4ncm361w3536asho7dr38iyookwcsp6i"
     );
 }
+
+#[test]
+fn test_render_single_element_tuple() {
+    assert_eq!(Value::Tuple(vec![Value::Nil]).to_string(), "(Nil,)");
+}
+
+#[test]
+fn test_parse_literals() {
+    let result: Value = r#"(Nil, [true, -1], Set("a"), {"b": "0aff".hexToBytes()}, `rho:id:c`)"#
+        .parse()
+        .unwrap();
+
+    let expected = Value::Tuple(vec![
+        Value::Nil,
+        Value::List(vec![Value::Bool(true), Value::Int(-1)]),
+        Value::Set(std::iter::once(Value::String("a".into())).collect()),
+        Value::Map(std::iter::once(("b".to_owned(), Value::Bytes(vec![0x0a, 0xff]))).collect()),
+        Value::Uri("rho:id:c".into()),
+    ]);
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_parse_parenthesized_value_is_not_tuple() {
+    assert_eq!("(1)".parse::<Value>().unwrap(), Value::Int(1));
+    assert_eq!(
+        "(1,)".parse::<Value>().unwrap(),
+        Value::Tuple(vec![Value::Int(1)])
+    );
+}
+
+#[test]
+fn test_parse_rejects_code() {
+    let cases = [
+        "new x in { Nil }",
+        "Nil | Nil",
+        "\"foo",
+        "[1, 2",
+        "Nilx",
+        "{1: 2}",
+    ];
+    for case in cases {
+        assert!(case.parse::<Value>().is_err(), "{case} should be rejected");
+    }
+}
+
+#[test]
+fn test_parse_rejects_too_deep_nesting() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+
+    assert!(nested(MAX_PARSE_DEPTH).parse::<Value>().is_ok());
+
+    let err = nested(100_000).parse::<Value>().unwrap_err();
+    assert_eq!(err.position, MAX_PARSE_DEPTH + 1);
+    assert_eq!(
+        err.message,
+        format!("nesting deeper than {MAX_PARSE_DEPTH}")
+    );
+}
+
+fn value_strategy() -> impl Strategy<Value = Value> {
+    let leaf = prop_oneof![
+        Just(Value::Nil),
+        any::<bool>().prop_map(Value::Bool),
+        any::<i64>().prop_map(Value::Int),
+        any::<String>().prop_map(Value::String),
+        any::<Vec<u8>>().prop_map(Value::Bytes),
+        "[a-zA-Z0-9:/._-]*".prop_map(Value::Uri),
+    ];
+
+    leaf.prop_recursive(4, 64, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(Value::Tuple),
+            prop::collection::vec(inner.clone(), 0..8).prop_map(Value::List),
+            prop::collection::btree_set(inner.clone(), 0..8).prop_map(Value::Set),
+            prop::collection::btree_map(any::<String>(), inner, 0..8).prop_map(Value::Map),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn test_parse_display_round_trip(value in value_strategy()) {
+        let rendered = value.to_string();
+        prop_assert_eq!(rendered.parse::<Value>(), Ok(value));
+    }
+}
@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use firefly_client::errors::SignedContractError;
+use firefly_client::models::casper::DeployDataProto;
+use firefly_client::models::{SignedCode, Uri, WalletAddress};
+use firefly_signing::UnsignedDeploy;
+use proptest::prelude::*;
+use prost::Message;
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+proptest! {
+    #[test]
+    fn test_unsigned_deploy_matches_proto(
+        term in ".*",
+        timestamp: i64,
+        phlo_price: i64,
+        phlo_limit: i64,
+        valid_after_block_number: i64,
+        shard_id in "[a-z]*",
+    ) {
+        let deploy = UnsignedDeploy {
+            term: term.clone(),
+            timestamp,
+            phlo_price,
+            phlo_limit,
+            valid_after_block_number,
+            shard_id: shard_id.clone(),
+        };
+
+        let proto = DeployDataProto {
+            term,
+            timestamp,
+            phlo_price,
+            phlo_limit,
+            valid_after_block_number,
+            shard_id,
+            ..Default::default()
+        };
+
+        prop_assert_eq!(deploy.encode(), proto.encode_to_vec());
+    }
+}
+
+#[test]
+fn test_signed_contract_verifies() {
+    let secp = Secp256k1::new();
+    let key =
+        SecretKey::from_str("f450b26bac63e5dd9343cd46f5fae1986d367a893cd21eedd98a4cb3ac699abc")
+            .unwrap();
+    let contract = UnsignedDeploy {
+        term: "Nil".into(),
+        timestamp: 1_559_156_356_769,
+        phlo_limit: 500_000,
+        phlo_price: 1,
+        ..Default::default()
+    }
+    .encode();
+
+    let sig = firefly_signing::sign_contract(&key, &contract);
+    let deployer = PublicKey::from_slice(&firefly_signing::deployer(&key)).unwrap();
+
+    let message = secp256k1::Message::from_digest(firefly_signing::contract_hash(&contract));
+    let sig = secp256k1::ecdsa::Signature::from_der(&sig).unwrap();
+    assert!(secp.verify_ecdsa(message, &sig, &deployer).is_ok());
+
+    let address = firefly_signing::wallet_address(&deployer);
+    assert_eq!(
+        WalletAddress::try_from(address).unwrap(),
+        WalletAddress::from(deployer)
+    );
+
+    let uri = firefly_signing::uri(&deployer);
+    assert_eq!(Uri::try_from(uri).unwrap(), Uri::from(deployer));
+}
+
+#[test]
+fn test_signed_code_verify_rejections() {
+    let secp = Secp256k1::new();
+    let key =
+        SecretKey::from_str("f450b26bac63e5dd9343cd46f5fae1986d367a893cd21eedd98a4cb3ac699abc")
+            .unwrap();
+    let other =
+        SecretKey::from_str("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90")
+            .unwrap();
+    let prepared = UnsignedDeploy {
+        term: "Nil".into(),
+        timestamp: 1_559_156_356_769,
+        ..Default::default()
+    }
+    .encode();
+
+    let signed = SignedCode {
+        contract: prepared.clone(),
+        sig: firefly_signing::sign_contract(&key, &prepared),
+        sig_algorithm: firefly_signing::SIG_ALGORITHM.into(),
+        deployer: firefly_signing::deployer(&key).into(),
+    };
+    let owner = WalletAddress::from(key.public_key(&secp));
+    assert!(signed.verify(&prepared, Some(&owner)).is_ok());
+
+    let mut tampered = prepared.clone();
+    tampered.push(0);
+    assert!(matches!(
+        signed.verify(&tampered, None),
+        Err(SignedContractError::ContractMismatch)
+    ));
+
+    let forged = SignedCode {
+        deployer: firefly_signing::deployer(&other).into(),
+        ..signed.clone()
+    };
+    assert!(matches!(
+        forged.verify(&prepared, None),
+        Err(SignedContractError::InvalidSignature)
+    ));
+
+    let stranger = WalletAddress::from(other.public_key(&secp));
+    assert!(matches!(
+        signed.verify(&prepared, Some(&stranger)),
+        Err(SignedContractError::DeployerMismatch { .. })
+    ));
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use firefly_client::fake_node::FakeNode;
+use firefly_client::models::DeployData;
+use firefly_client::rendering::Value;
+use futures::StreamExt;
+use secp256k1::SecretKey;
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn test_watch_channel_follows_new_blocks() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+
+    for value in ["a", "b"] {
+        node.set_data_at_name("counter", [Value::String(value.into())]);
+        client
+            .deploy(&key(), DeployData::builder(value.into()).build())
+            .await
+            .unwrap();
+        client.propose().await.unwrap();
+    }
+
+    let mut data =
+        Box::pin(client.watch_channel::<String>("counter".into(), 2, Duration::from_millis(50)));
+
+    let first = data.next().await.unwrap().unwrap();
+    assert_eq!((first.block_number, first.value.as_str()), (2, "b"));
+
+    node.set_data_at_name("counter", [Value::String("c".into())]);
+    client
+        .deploy(&key(), DeployData::builder("c".into()).build())
+        .await
+        .unwrap();
+    client.propose().await.unwrap();
+
+    let next = tokio::time::timeout(Duration::from_secs(5), data.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!((next.block_number, next.value.as_str()), (3, "c"));
+    assert_eq!(
+        next.block.to_string(),
+        node.blocks()[3].block_info.as_ref().unwrap().block_hash
+    );
+}
+
+#[tokio::test]
+async fn test_watch_channel_pages_through_old_blocks() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+
+    let mut propose = async |value: &str| {
+        node.set_data_at_name("counter", [Value::String(value.into())]);
+        client
+            .deploy(
+                &key(),
+                DeployData::builder(format!("{value} {}", node.blocks().len())).build(),
+            )
+            .await
+            .unwrap();
+        client.propose().await.unwrap();
+    };
+
+    for i in 0..60 {
+        propose(&i.to_string()).await;
+    }
+    // post-state that stays the same is not yielded again
+    propose("59").await;
+    propose("59").await;
+    propose("last").await;
+
+    let data: Vec<_> = client
+        .watch_channel::<String>("counter".into(), 1, Duration::from_millis(50))
+        .take(61)
+        .map(|data| {
+            let data = data.unwrap();
+            (data.block_number, data.value)
+        })
+        .collect()
+        .await;
+
+    let expected: Vec<_> = (0..60)
+        .map(|i| (i + 1, i.to_string()))
+        .chain([(63, "last".to_owned())])
+        .collect();
+    assert_eq!(data, expected);
+}
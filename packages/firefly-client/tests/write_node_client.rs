@@ -0,0 +1,139 @@
+use firefly_client::WriteNodeClient;
+use firefly_client::errors::{ServiceError, WriteNodeError};
+use firefly_client::fake_node::FakeNode;
+use firefly_client::models::{DeployData, ValidAfter};
+use secp256k1::SecretKey;
+
+fn key() -> SecretKey {
+    SecretKey::from_byte_array([1; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn test_deploys_are_proposed_by_validator_they_went_to() {
+    let first = FakeNode::start().await.unwrap();
+    let second = FakeNode::start().await.unwrap();
+
+    let mut client = WriteNodeClient::with_validators([
+        (first.grpc_url(), first.grpc_url()),
+        (second.grpc_url(), second.grpc_url()),
+    ])
+    .await
+    .unwrap();
+    assert_eq!(client.check_health().await, 2);
+
+    for _ in 0..2 {
+        let deploy_data = DeployData::builder("Nil".into())
+            .valid_after_block_number(ValidAfter::Index(0))
+            .build();
+        client.deploy(&key(), deploy_data).await.unwrap();
+    }
+    let block = client.propose().await.unwrap();
+
+    assert_eq!(first.deploys().len(), 2);
+    assert!(second.deploys().is_empty());
+    assert_eq!(client.get_block(&block).await.unwrap().deploys.len(), 2);
+    assert_eq!(second.blocks().len(), 1);
+}
+
+#[tokio::test]
+async fn test_failover_to_healthy_validator() {
+    let down = FakeNode::start().await.unwrap();
+    let down_url = down.grpc_url();
+    drop(down);
+    let up = FakeNode::start().await.unwrap();
+
+    let mut client = WriteNodeClient::with_validators([
+        (down_url.clone(), down_url),
+        (up.grpc_url(), up.grpc_url()),
+    ])
+    .await
+    .unwrap();
+    assert_eq!(client.check_health().await, 1);
+
+    for _ in 0..3 {
+        client
+            .deploy(&key(), DeployData::builder("Nil".into()).build())
+            .await
+            .unwrap();
+    }
+    assert_eq!(up.deploys().len(), 3);
+
+    client.propose().await.unwrap();
+    assert_eq!(up.blocks().len(), 2);
+}
+
+#[tokio::test]
+async fn test_lost_deploy_response_is_retried_on_same_validator() {
+    let first = FakeNode::start().await.unwrap();
+    let second = FakeNode::start().await.unwrap();
+    first.drop_deploy_responses(1);
+
+    let mut client = WriteNodeClient::with_validators([
+        (first.grpc_url(), first.grpc_url()),
+        (second.grpc_url(), second.grpc_url()),
+    ])
+    .await
+    .unwrap();
+
+    let deploy_id = client
+        .deploy(&key(), DeployData::builder("Nil".into()).build())
+        .await
+        .unwrap();
+
+    let deploys = first.deploys();
+    assert_eq!(deploys.len(), 1);
+    assert_eq!(hex::encode(&deploys[0].sig), deploy_id.to_string());
+    assert!(second.deploys().is_empty());
+
+    let block = client.propose().await.unwrap();
+    assert_eq!(client.get_block(&block).await.unwrap().deploys.len(), 1);
+}
+
+#[tokio::test]
+async fn test_deploy_taken_in_before_lost_response_is_not_duplicate() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+    let deploy = DeployData::builder("Nil".into())
+        .valid_after_block_number(ValidAfter::Index(0))
+        .build();
+
+    node.drop_deploy_responses(1);
+    let deploy_id = client.deploy(&key(), deploy.clone()).await.unwrap();
+    assert_eq!(node.deploys().len(), 1);
+
+    let err = client.deploy(&key(), deploy).await.unwrap_err();
+    assert!(matches!(
+        err,
+        WriteNodeError::Service(ServiceError::DuplicateDeploy(_))
+    ));
+    assert_eq!(hex::encode(&node.deploys()[0].sig), deploy_id.to_string());
+}
+
+#[tokio::test]
+async fn test_deploy_lookups() {
+    let node = FakeNode::start().await.unwrap();
+    let mut client = node.write_client().await.unwrap();
+
+    let deploy_id = client
+        .deploy(&key(), DeployData::builder("Nil".into()).build())
+        .await
+        .unwrap();
+    assert_eq!(client.find_deploy(&deploy_id).await.unwrap(), None);
+
+    let block_id = client.propose().await.unwrap();
+    let found = client.find_deploy(&deploy_id).await.unwrap().unwrap();
+    assert_eq!(found.block_hash, block_id.to_string());
+
+    let block = client.get_block(&block_id).await.unwrap();
+    assert_eq!(block.deploys.len(), 1);
+    assert_eq!(block.deploys[0].sig, deploy_id.to_string());
+
+    assert!(client.is_finalized(&block_id).await.unwrap());
+    assert!(!client.is_finalized(&"00".to_owned().into()).await.unwrap());
+
+    let last_finalized = client.last_finalized_block().await.unwrap();
+    assert_eq!(
+        last_finalized.block_info.unwrap().block_hash,
+        block_id.to_string()
+    );
+}
@@ -0,0 +1,185 @@
+//! Offline part of the prepare → sign → send deploy protocol.
+//!
+//! Everything a client needs to sign deploys the way nodes verify them, without
+//! network access or `std`, so it also builds for `wasm32-unknown-unknown`
+//! (JS bindings are behind the `wasm` feature):
+//!
+//! ```sh
+//! cargo rustc -p firefly-signing --release --features wasm \
+//!     --target wasm32-unknown-unknown --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/firefly_signing.wasm
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use crc::Crc;
+use prost::Message as _;
+use secp256k1::ecdsa::Signature;
+use secp256k1::{Message, Secp256k1};
+pub use secp256k1::{PublicKey, SecretKey};
+
+mod proto;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod zbase32;
+
+/// Value of `sigAlgorithm` field of deploys signed by [`sign_contract`].
+pub const SIG_ALGORITHM: &str = "secp256k1";
+
+pub const FIRECAP_ID: [u8; 3] = [0, 0, 0];
+pub const FIRECAP_VERSION: u8 = 0;
+
+/// Checksum of `rho:id:` uris.
+pub const URI_CRC: crc::Algorithm<u16> = crc::Algorithm {
+    width: 14,
+    poly: 0x4805,
+    init: 0x0000,
+    refin: false,
+    refout: false,
+    xorout: 0x0000,
+    check: 0,
+    residue: 0x0000,
+};
+
+/// Deploy fields covered by signature.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UnsignedDeploy {
+    pub term: String,
+    /// Milliseconds since epoch.
+    pub timestamp: i64,
+    pub phlo_price: i64,
+    pub phlo_limit: i64,
+    pub valid_after_block_number: i64,
+    pub shard_id: String,
+}
+
+impl UnsignedDeploy {
+    /// Encodes deploy as `DeployDataProto` with empty signature fields, the bytes that get signed.
+    pub fn encode(&self) -> Vec<u8> {
+        proto::DeployDataProto {
+            term: self.term.clone(),
+            timestamp: self.timestamp,
+            phlo_price: self.phlo_price,
+            phlo_limit: self.phlo_limit,
+            valid_after_block_number: self.valid_after_block_number,
+            shard_id: self.shard_id.clone(),
+            ..Default::default()
+        }
+        .encode_to_vec()
+    }
+}
+
+/// Blake2b-256 of encoded contract, the digest nodes verify signature against.
+pub fn contract_hash(contract: &[u8]) -> [u8; 32] {
+    Blake2b::<U32>::new()
+        .chain_update(contract)
+        .finalize()
+        .into()
+}
+
+/// Signs encoded contract, returning DER encoded signature.
+pub fn sign_contract(key: &SecretKey, contract: &[u8]) -> Vec<u8> {
+    sign(key, contract_hash(contract))
+}
+
+/// Checks that DER encoded `sig` is `deployer`'s signature of encoded contract.
+///
+/// High-S signatures are accepted, the same way nodes accept them.
+pub fn verify_contract(deployer: &PublicKey, contract: &[u8], sig: &[u8]) -> bool {
+    let Ok(mut sig) = Signature::from_der(sig) else {
+        return false;
+    };
+    sig.normalize_s();
+
+    Secp256k1::verification_only()
+        .verify_ecdsa(
+            Message::from_digest(contract_hash(contract)),
+            &sig,
+            deployer,
+        )
+        .is_ok()
+}
+
+/// Public key in the form deploys carry it as `deployer`.
+pub fn deployer(key: &SecretKey) -> [u8; 65] {
+    key.public_key(&Secp256k1::signing_only())
+        .serialize_uncompressed()
+}
+
+pub fn wallet_address(key: &PublicKey) -> String {
+    let key_hash: [u8; 32] = sha3::Keccak256::new()
+        .chain_update(&key.serialize_uncompressed()[1..])
+        .finalize()
+        .into();
+
+    let eth_hash = sha3::Keccak256::new()
+        .chain_update(&key_hash[key_hash.len() - 20..])
+        .finalize();
+
+    let checksum_hash: [u8; 32] = Blake2b::<U32>::new()
+        .chain_update(FIRECAP_ID)
+        .chain_update([FIRECAP_VERSION])
+        .chain_update(eth_hash)
+        .finalize()
+        .into();
+
+    let checksum = &checksum_hash[0..4];
+
+    let address_bytes = [
+        FIRECAP_ID.as_ref(),
+        [FIRECAP_VERSION].as_ref(),
+        eth_hash.as_ref(),
+        checksum,
+    ]
+    .concat();
+
+    bs58::encode(address_bytes).into_string()
+}
+
+/// `rho:id:` uri registry derives from the public key.
+pub fn uri(key: &PublicKey) -> String {
+    let hash = Blake2b::<U32>::new()
+        .chain_update(key.serialize_uncompressed())
+        .finalize();
+
+    let crc = Crc::<u16>::new(&URI_CRC).checksum(&hash).to_ne_bytes();
+    let full_key = [hash.as_ref(), [crc[0], crc[1] << 2].as_ref()].concat();
+    let mut uri = String::from("rho:id:");
+    uri.push_str(&zbase32::encode(&full_key, 270));
+    uri
+}
+
+/// Signature `insertSigned` registry contract expects for `(timestamp, deployer, version)`.
+pub fn insert_signed_signature(
+    key: &SecretKey,
+    timestamp: i64,
+    deployer: &PublicKey,
+    version: i64,
+) -> Vec<u8> {
+    let data = proto::tuple(alloc::vec![
+        proto::Expr::Int(timestamp),
+        proto::Expr::ByteArray(deployer.serialize_uncompressed().into()),
+        proto::Expr::Int(version),
+    ])
+    .encode_to_vec();
+
+    sign(
+        key,
+        Blake2b::<U32>::new().chain_update(data).finalize().into(),
+    )
+}
+
+fn sign(key: &SecretKey, digest: [u8; 32]) -> Vec<u8> {
+    Secp256k1::signing_only()
+        .sign_ecdsa(Message::from_digest(digest), key)
+        .serialize_der()
+        .to_vec()
+}
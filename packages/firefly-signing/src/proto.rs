@@ -0,0 +1,67 @@
+//! Subset of node protobuf messages needed for signing, with the same field tags.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct DeployDataProto {
+    #[prost(bytes = "vec", tag = "1")]
+    pub deployer: Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub term: String,
+    #[prost(int64, tag = "3")]
+    pub timestamp: i64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub sig: Vec<u8>,
+    #[prost(string, tag = "5")]
+    pub sig_algorithm: String,
+    #[prost(int64, tag = "7")]
+    pub phlo_price: i64,
+    #[prost(int64, tag = "8")]
+    pub phlo_limit: i64,
+    #[prost(int64, tag = "10")]
+    pub valid_after_block_number: i64,
+    #[prost(string, tag = "11")]
+    pub shard_id: String,
+}
+
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct Par {
+    #[prost(message, repeated, tag = "5")]
+    pub exprs: Vec<ExprMessage>,
+}
+
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ExprMessage {
+    #[prost(oneof = "Expr", tags = "2, 25, 21")]
+    pub expr_instance: Option<Expr>,
+}
+
+#[derive(Clone, PartialEq, Eq, prost::Oneof)]
+pub enum Expr {
+    #[prost(sint64, tag = "2")]
+    Int(i64),
+    #[prost(bytes, tag = "25")]
+    ByteArray(Vec<u8>),
+    #[prost(message, tag = "21")]
+    Tuple(ETuple),
+}
+
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct ETuple {
+    #[prost(message, repeated, tag = "1")]
+    pub ps: Vec<Par>,
+}
+
+pub fn tuple(elements: Vec<Expr>) -> Par {
+    let ps = elements.into_iter().map(par).collect();
+    par(Expr::Tuple(ETuple { ps }))
+}
+
+fn par(expr: Expr) -> Par {
+    Par {
+        exprs: alloc::vec![ExprMessage {
+            expr_instance: Some(expr),
+        }],
+    }
+}
@@ -0,0 +1,88 @@
+//! JS bindings, byte arrays are `Uint8Array`s and 64 bit integers are `BigInt`s.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use secp256k1::{PublicKey, SecretKey};
+use wasm_bindgen::prelude::*;
+
+use crate::UnsignedDeploy;
+
+#[wasm_bindgen(getter_with_clone)]
+pub struct SignedContract {
+    pub sig: Vec<u8>,
+    #[wasm_bindgen(js_name = sigAlgorithm)]
+    pub sig_algorithm: String,
+    pub deployer: Vec<u8>,
+}
+
+#[wasm_bindgen(js_name = encodeDeploy)]
+pub fn encode_deploy(
+    term: String,
+    timestamp: i64,
+    phlo_price: i64,
+    phlo_limit: i64,
+    valid_after_block_number: i64,
+    shard_id: String,
+) -> Vec<u8> {
+    UnsignedDeploy {
+        term,
+        timestamp,
+        phlo_price,
+        phlo_limit,
+        valid_after_block_number,
+        shard_id,
+    }
+    .encode()
+}
+
+#[wasm_bindgen(js_name = signContract)]
+pub fn sign_contract(secret_key: &[u8], contract: &[u8]) -> Result<SignedContract, JsError> {
+    let key = secret_key_from(secret_key)?;
+    Ok(SignedContract {
+        sig: crate::sign_contract(&key, contract),
+        sig_algorithm: crate::SIG_ALGORITHM.into(),
+        deployer: crate::deployer(&key).into(),
+    })
+}
+
+#[wasm_bindgen(js_name = publicKey)]
+pub fn public_key(secret_key: &[u8]) -> Result<Vec<u8>, JsError> {
+    Ok(crate::deployer(&secret_key_from(secret_key)?).into())
+}
+
+#[wasm_bindgen(js_name = walletAddress)]
+pub fn wallet_address(public_key: &[u8]) -> Result<String, JsError> {
+    Ok(crate::wallet_address(&public_key_from(public_key)?))
+}
+
+#[wasm_bindgen]
+pub fn uri(public_key: &[u8]) -> Result<String, JsError> {
+    Ok(crate::uri(&public_key_from(public_key)?))
+}
+
+#[wasm_bindgen(js_name = insertSignedSignature)]
+pub fn insert_signed_signature(
+    secret_key: &[u8],
+    timestamp: i64,
+    deployer: &[u8],
+    version: i64,
+) -> Result<Vec<u8>, JsError> {
+    Ok(crate::insert_signed_signature(
+        &secret_key_from(secret_key)?,
+        timestamp,
+        &public_key_from(deployer)?,
+        version,
+    ))
+}
+
+fn secret_key_from(bytes: &[u8]) -> Result<SecretKey, JsError> {
+    let bytes: [u8; 32] = bytes
+        .try_into()
+        .map_err(|_| JsError::new("secret key must be 32 bytes"))?;
+    SecretKey::from_byte_array(bytes).map_err(|_| JsError::new("invalid secret key"))
+}
+
+fn public_key_from(bytes: &[u8]) -> Result<PublicKey, JsError> {
+    PublicKey::from_slice(bytes).map_err(|_| JsError::new("invalid public key"))
+}
@@ -0,0 +1,23 @@
+//! zbase32 encoding, the one `rho:id:` uris use.
+
+use alloc::string::String;
+
+const ALPHABET: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+/// Encodes first `bits` bits of `data`, most significant bit first.
+pub fn encode(data: &[u8], bits: usize) -> String {
+    let bit = |index: usize| {
+        data.get(index / 8)
+            .map_or(0, |byte| (byte >> (7 - index % 8)) & 1)
+    };
+
+    (0..bits)
+        .step_by(5)
+        .map(|start| {
+            let value = (start..start + 5).fold(0, |value, index| {
+                (value << 1) | if index < bits { bit(index) } else { 0 }
+            });
+            char::from(ALPHABET[usize::from(value)])
+        })
+        .collect()
+}
@@ -1,25 +1,22 @@
-use std::collections::HashMap;
 use std::fmt::Display;
 use std::process::Command;
 use std::time::Duration;
 
-use anyhow::{Context, Ok, anyhow};
+use anyhow::{Ok, anyhow};
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
 use clap::{Parser, Subcommand};
 use firefly_client::helpers::FromExpr;
-use firefly_client::models::rhoapi::expr::ExprInstance;
+use firefly_client::keystore::WalletKeyArgs;
 use firefly_client::models::{BlockId, DeployData};
-use secp256k1::SecretKey;
 use serde::{Deserialize, Serialize};
 use tokio::select;
 use uuid::Uuid;
 
 #[derive(Debug, Parser)]
 struct Args {
-    /// Wallet key in hex format
-    #[arg(long)]
-    wallet_key: SecretKey,
+    #[command(flatten)]
+    wallet: WalletKeyArgs,
 
     /// Firefly deploy service url
     #[arg(long)]
@@ -64,6 +61,7 @@ enum Commands {
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
+    let wallet_key = args.wallet.load()?;
 
     let mut client =
         firefly_client::WriteNodeClient::new(args.deploy_service_url, args.propose_service_url)
@@ -86,7 +84,7 @@ async fn main() -> anyhow::Result<()> {
 
                 let rho_code = rho_sql_dump_template(channel_name, sql);
                 let deploy_data = DeployData::builder(rho_code).build();
-                let hash = client.full_deploy(&args.wallet_key, deploy_data).await?;
+                let hash = client.full_deploy(&wallet_key, deploy_data).await?;
                 println!("dump hash: {hash}");
 
                 let rho_code = rho_save_hash_template(
@@ -97,14 +95,17 @@ async fn main() -> anyhow::Result<()> {
                     },
                 );
                 let deploy_data = DeployData::builder(rho_code).build();
-                let hash = client.full_deploy(&args.wallet_key, deploy_data).await?;
+                let hash = client.full_deploy(&wallet_key, deploy_data).await?;
                 println!("save hash: {hash}");
             }
         }
         Commands::Download { hash } => {
             let entries: Vec<ServiceHash> = client
                 .get_channel_value(hash, format!("{}-hashes", args.service_id))
-                .await?;
+                .await?
+                .pop()
+                .ok_or_else(|| anyhow!("no data"))?
+                .value;
 
             let Some(entry) = entries.into_iter().next_back() else {
                 return Err(anyhow!("no data"));
@@ -112,7 +113,10 @@ async fn main() -> anyhow::Result<()> {
 
             let sql: String = client
                 .get_channel_value(entry.block_hash, entry.channel_name.to_string())
-                .await?;
+                .await?
+                .pop()
+                .ok_or_else(|| anyhow!("no data"))?
+                .value;
             let sql = BASE64_STANDARD.decode(sql)?;
             let sql = String::from_utf8(sql)?;
             println!("{sql}");
@@ -120,7 +124,7 @@ async fn main() -> anyhow::Result<()> {
         Commands::Init => {
             let rho_code = rho_save_hash_contract(&args.service_id);
             let deploy_data = DeployData::builder(rho_code).build();
-            let hash = client.full_deploy(&args.wallet_key, deploy_data).await?;
+            let hash = client.full_deploy(&wallet_key, deploy_data).await?;
             println!("{hash}");
         }
     }
@@ -153,29 +157,12 @@ fn rho_save_hash_contract(service_id: &str) -> String {
     )
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, FromExpr)]
 struct ServiceHash {
     block_hash: BlockId,
     channel_name: Uuid,
 }
 
-impl FromExpr for ServiceHash {
-    fn from(val: ExprInstance) -> anyhow::Result<Self> {
-        let mut map: HashMap<String, String> = FromExpr::from(val)?;
-
-        let block_hash = map.remove("block_hash").context("block_hash is missing")?;
-        let channel_name = map
-            .remove("channel_name")
-            .context("channel_name is missing")?;
-        let channel_name: Uuid = channel_name.parse()?;
-
-        Ok(Self {
-            block_hash: block_hash.into(),
-            channel_name,
-        })
-    }
-}
-
 fn run_pg_dump(db_url: &str) -> anyhow::Result<String> {
     let mut command = Command::new("pg_dump");
 